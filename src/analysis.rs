@@ -0,0 +1,185 @@
+use std::f64::consts::LN_2;
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Row, Table},
+    Frame,
+};
+
+use crate::get_word_state;
+use crate::player::Game;
+use crate::server::{word_basic_check, word_state_index, Server};
+
+/// analysis of one guess, information is in bits
+pub struct GuessAnalysis {
+    pub guess: String,
+    /// number of possible answers before this guess
+    pub candidates_before: usize,
+    /// number of possible answers after this guess
+    pub candidates_after: usize,
+    /// expected information of this guess
+    pub expected_bits: f64,
+    /// information actually gained by this guess
+    pub actual_bits: f64,
+    /// the guess with max expected information, None if there are too many possible answers
+    pub best: Option<(String, f64)>,
+    /// expected information compared with the best guess, 0 ~ 100
+    pub skill: Option<f64>,
+    /// chance that the feedback is worse than the actual one, 0 ~ 100
+    pub luck: f64,
+}
+
+/// analysis of a finished game
+pub struct GameAnalysis {
+    pub guesses: Vec<GuessAnalysis>,
+    /// average skill of guesses
+    pub skill: Option<f64>,
+    /// average luck of guesses
+    pub luck: f64,
+}
+
+/// compare every guess of a game with the entropy-optimal guess
+pub fn analyze_game(server: &mut Server, game: &Game) -> GameAnalysis {
+    let answer = game.answer.to_uppercase();
+    let mut word_states: Vec<Vec<u8>> = Vec::new();
+    let mut guess_words: Vec<String> = Vec::new();
+    let mut guesses: Vec<GuessAnalysis> = Vec::new();
+    // a game in lying mode is analyzed with the feedback shown to the player
+    let fibble = server.fibble;
+    server.fibble = !game.feedback.is_empty();
+
+    if word_basic_check(&answer) {
+        server.get_all_possible_answers(&word_states, &guess_words);
+        for (index, guess) in game.guesses.iter().enumerate() {
+            let guess = guess.to_uppercase();
+            if !word_basic_check(&guess) {
+                continue;
+            }
+            let candidates_before = server.possible_answer.len();
+            let best = server.best_guess().map(|(word, entropy)| (word, entropy.abs() / LN_2));
+            let pattern_counts = server.pattern_counts(&guess);
+            let word_state = match game.feedback.get(index) {
+                Some(feedback) => feedback.as_bytes().to_vec(),
+                None => get_word_state(&answer, &guess),
+            };
+
+            word_states.push(word_state.clone());
+            guess_words.push(guess.clone());
+            server.get_all_possible_answers(&word_states, &guess_words);
+            let candidates_after = server.possible_answer.len();
+
+            let expected_bits = pattern_entropy(&pattern_counts);
+            let actual_bits = if candidates_after == 0 {
+                0.0
+            } else {
+                (candidates_before as f64 / candidates_after as f64).log2()
+            };
+            let skill = best.as_ref().map(|(word, best_bits)| guess_skill(&guess, expected_bits, word, *best_bits));
+            let luck = pattern_luck(&pattern_counts, pattern_counts[word_state_index(&word_state)]);
+
+            guesses.push(GuessAnalysis {
+                guess,
+                candidates_before,
+                candidates_after,
+                expected_bits,
+                actual_bits,
+                best,
+                skill,
+                luck,
+            });
+        }
+    }
+
+    server.fibble = fibble;
+
+    let skills: Vec<f64> = guesses.iter().filter_map(|x| x.skill).collect();
+    let skill = if skills.is_empty() { None } else { Some(skills.iter().sum::<f64>() / skills.len() as f64) };
+    let luck = if guesses.is_empty() { 0.0 } else { guesses.iter().map(|x| x.luck).sum::<f64>() / guesses.len() as f64 };
+    GameAnalysis { guesses, skill, luck }
+}
+
+/// entropy in bits of the word state distribution
+fn pattern_entropy(pattern_counts: &[i32]) -> f64 {
+    let total_count: i32 = pattern_counts.iter().sum();
+    if total_count == 0 {
+        return 0.0;
+    }
+    pattern_counts
+        .iter()
+        .filter(|x| **x != 0)
+        .map(|x| *x as f64 / total_count as f64)
+        .map(|x| x * (1.0 / x).log2())
+        .sum()
+}
+
+fn guess_skill(guess: &String, expected_bits: f64, best_word: &String, best_bits: f64) -> f64 {
+    // only one possible answer left, the best is to guess it
+    if best_bits <= f64::EPSILON {
+        return if guess == best_word { 100.0 } else { 0.0 };
+    }
+    (expected_bits / best_bits * 100.0).min(100.0)
+}
+
+/// probability of leaving more possible answers than `actual_count`, ties count half
+fn pattern_luck(pattern_counts: &[i32], actual_count: i32) -> f64 {
+    let total_count: i32 = pattern_counts.iter().sum();
+    if total_count == 0 {
+        return 50.0;
+    }
+    let mut worse = 0.0;
+    for count in pattern_counts {
+        if *count > actual_count {
+            worse += *count as f64;
+        } else if *count == actual_count {
+            worse += *count as f64 / 2.0;
+        }
+    }
+    worse / total_count as f64 * 100.0
+}
+
+/// analysis table of the finished game
+pub fn draw_analysis<B: Backend>(f: &mut Frame<B>, analysis: &GameAnalysis, area: Rect) {
+    let rows: Vec<Row> = analysis.guesses
+        .iter()
+        .map(|x| {
+            let best = match &x.best {
+                Some((word, bits)) => format!("{} {:.2}", word, bits),
+                None => "-".to_string(),
+            };
+            let skill = match x.skill {
+                Some(skill) => format!("{:.0}", skill),
+                None => "-".to_string(),
+            };
+            Row::new(vec![
+                x.guess.clone(),
+                format!("{}->{}", x.candidates_before, x.candidates_after),
+                format!("{:.2}", x.expected_bits),
+                format!("{:.2}", x.actual_bits),
+                best,
+                skill,
+                format!("{:.0}", x.luck),
+            ])
+        })
+        .collect();
+    let skill = match analysis.skill {
+        Some(skill) => format!("{:.0}", skill),
+        None => "-".to_string(),
+    };
+    let title = format!("Analysis: skill {}, luck {:.0}", skill, analysis.luck);
+    let widths = [
+        Constraint::Length(6),
+        Constraint::Length(11),
+        Constraint::Length(5),
+        Constraint::Length(5),
+        Constraint::Length(11),
+        Constraint::Length(5),
+        Constraint::Length(4),
+    ];
+    let table = Table::new(rows)
+        .header(Row::new(vec!["Guess", "Candidates", "Exp", "Got", "Best", "Skill", "Luck"])
+            .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)))
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .widths(&widths);
+    f.render_widget(table, area);
+}
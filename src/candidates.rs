@@ -0,0 +1,131 @@
+use std::f64::consts::LN_2;
+use crossterm::event::KeyCode;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::Span,
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use crate::App;
+use crate::cli::Cli;
+use crate::server::{Server, SOLVER_LIMIT};
+
+/// order of the candidate list
+#[derive(PartialEq, Clone, Copy)]
+pub enum CandidateSort {
+    Alphabet,
+    /// expected information in bits, falls back to alphabet if there are too many candidates
+    Score,
+}
+
+/// possible answers shown in the game, --candidates
+pub struct Candidates {
+    /// words and their scores in bits, no score if sorted by alphabet
+    pub words: Vec<(String, Option<f64>)>,
+    pub sort: CandidateSort,
+    pub state: ListState,
+}
+
+/// sort given by --candidates, alpha or score
+pub fn parse_sort(sort: &str) -> Result<CandidateSort, Box<dyn std::error::Error>> {
+    match sort {
+        "alpha" => Ok(CandidateSort::Alphabet),
+        "score" => Ok(CandidateSort::Score),
+        _ => Err(format!("unknown candidate sort {}, should be alpha or score!", sort).into()),
+    }
+}
+
+/// refresh the possible answers after the guesses, the sort and the scroll are kept
+pub fn update(cli: &Cli, server: &mut Server, word_states: &Vec<Vec<u8>>, guess_words: &Vec<String>, app: &mut App) {
+    let sort = match (&app.candidates, &cli.candidates) {
+        (Some(candidates), _) => candidates.sort,
+        (None, Some(sort)) => parse_sort(sort).unwrap_or(CandidateSort::Alphabet),
+        (None, None) => return,
+    };
+    server.get_all_possible_answers(word_states, guess_words);
+
+    let words: Vec<(String, Option<f64>)> = if sort == CandidateSort::Score && server.possible_answer.len() <= SOLVER_LIMIT {
+        server.get_sorted_entropy().into_iter().map(|(word, entropy)| (word, Some(entropy.abs() / LN_2))).collect()
+    } else {
+        let mut words = server.possible_answer.clone();
+        words.sort();
+        words.into_iter().map(|word| (word, None)).collect()
+    };
+
+    let mut state = app.candidates.take().map(|x| x.state).unwrap_or_default();
+    state.select(if words.is_empty() { None } else { Some(state.selected().unwrap_or(0).min(words.len() - 1)) });
+    app.candidates = Some(Candidates { words, sort, state });
+}
+
+/// scroll the list or switch the sort, true if the key is used
+pub fn handle_key(cli: &Cli, server: &mut Server, word_states: &Vec<Vec<u8>>, guess_words: &Vec<String>, key: KeyCode, app: &mut App) -> bool {
+    let candidates = match &mut app.candidates {
+        Some(candidates) => candidates,
+        None => return false,
+    };
+    let len = candidates.words.len();
+    let selected = candidates.state.selected().unwrap_or(0);
+    let selected = match key {
+        KeyCode::Up => selected.saturating_sub(1),
+        KeyCode::Down => selected + 1,
+        KeyCode::PageUp => selected.saturating_sub(10),
+        KeyCode::PageDown => selected + 10,
+        KeyCode::F(9) => {
+            candidates.sort = match candidates.sort {
+                CandidateSort::Alphabet => CandidateSort::Score,
+                CandidateSort::Score => CandidateSort::Alphabet,
+            };
+            candidates.state.select(Some(0));
+            update(cli, server, word_states, guess_words, app);
+            return true;
+        }
+        _ => return false,
+    };
+    if len > 0 {
+        candidates.state.select(Some(selected.min(len - 1)));
+    }
+    true
+}
+
+/// "N words remaining" on the bottom border of `area`
+pub fn draw_remaining<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+    let candidates = match &app.candidates {
+        Some(candidates) => candidates,
+        None => return,
+    };
+    let text = format!(" {} words remaining ", candidates.words.len());
+    let width = (text.chars().count() as u16).min(area.width.saturating_sub(2));
+    let remaining_area = Rect::new(area.x + 1, area.y + area.height - 1, width, 1);
+    let paragraph = Paragraph::new(Span::styled(text, app.theme.message_style().add_modifier(Modifier::REVERSED)));
+    f.render_widget(paragraph, remaining_area);
+}
+
+/// scrollable list of the possible answers
+pub fn draw_candidates<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
+    let style = app.theme.panel_style();
+    let candidates = match &mut app.candidates {
+        Some(candidates) => candidates,
+        None => return,
+    };
+    let sort = match candidates.sort {
+        CandidateSort::Alphabet => "A-Z",
+        CandidateSort::Score if !matches!(candidates.words.first(), Some((_, None))) => "score",
+        CandidateSort::Score => "A-Z, too many to score",
+    };
+    let items: Vec<ListItem> = candidates.words
+        .iter()
+        .map(|(word, score)| match score {
+            Some(score) => ListItem::new(format!("{} {:.2}", word, score)),
+            None => ListItem::new(word.clone()),
+        })
+        .collect();
+    let title = format!("Candidates: {} ({}, F9: sort)", candidates.words.len(), sort);
+    let list = List::new(items)
+        .style(style)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    f.render_stateful_widget(list, area, &mut candidates.state);
+}
@@ -0,0 +1,177 @@
+use crate::server::Server;
+
+/// format of the challenge code
+const VERSION: u8 = 1;
+/// Crockford's base32, O is read as 0 and I/L as 1 when decoding
+const ALPHABET: &[u8] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+/// letters between dashes in the code
+const GROUP: usize = 4;
+const HARD: u8 = 1;
+
+/// a custom puzzle shared as a code, without showing the answer
+pub struct Challenge {
+    pub answer: String,
+    /// identity of the final word list the answer is taken from
+    pub list_id: u16,
+    pub max_guesses: u8,
+    pub difficult: bool,
+}
+
+impl Challenge {
+    pub fn new(answer: &str, server: &Server, difficult: bool) -> Result<Challenge, Box<dyn std::error::Error>> {
+        let answer = answer.trim().to_uppercase();
+        if !server.is_final(&answer) {
+            return Err(format!("{} is not in the final word list!", answer).into());
+        }
+        Ok(Challenge { answer, list_id: server.final_list_id(), max_guesses: 6, difficult })
+    }
+
+    pub fn encode(&self) -> String {
+        let mut payload = vec![self.answer.len() as u8, self.max_guesses, if self.difficult { HARD } else { 0 }];
+        payload.extend(self.list_id.to_be_bytes());
+        payload.extend(self.answer.bytes().map(|x| x - b'A'));
+        payload.extend(((fnv1a(&payload) & 0xffff) as u16).to_be_bytes());
+        mask(&mut payload);
+
+        let mut bytes = vec![VERSION];
+        bytes.append(&mut payload);
+        let code = base32_encode(&bytes);
+        code.as_bytes()
+            .chunks(GROUP)
+            .map(|x| String::from_utf8_lossy(x).to_string())
+            .collect::<Vec<String>>()
+            .join("-")
+    }
+
+    pub fn decode(code: &str) -> Result<Challenge, Box<dyn std::error::Error>> {
+        let invalid = || -> Box<dyn std::error::Error> { "invalid challenge code!".into() };
+        let code = code.replace('-', "").to_uppercase().replace('O', "0").replace(['I', 'L'], "1");
+        let bytes = base32_decode(&code).ok_or_else(invalid)?;
+        if bytes.first() != Some(&VERSION) || bytes.len() < 8 {
+            return Err(invalid());
+        }
+        let mut payload = bytes[1..].to_vec();
+        mask(&mut payload);
+        let (payload, checksum) = payload.split_at(payload.len() - 2);
+        if (fnv1a(payload) & 0xffff) as u16 != u16::from_be_bytes([checksum[0], checksum[1]]) {
+            return Err(invalid());
+        }
+
+        let length = payload[0] as usize;
+        let letters = &payload[5..];
+        if letters.len() != length || letters.iter().any(|x| *x >= 26) {
+            return Err(invalid());
+        }
+        Ok(Challenge {
+            answer: letters.iter().map(|x| (x + b'A') as char).collect(),
+            list_id: u16::from_be_bytes([payload[3], payload[4]]),
+            max_guesses: payload[1],
+            difficult: payload[2] & HARD != 0,
+        })
+    }
+
+    /// the challenge can be played with the word lists of `server`
+    pub fn check(&self, server: &Server) -> Result<(), Box<dyn std::error::Error>> {
+        if self.answer.len() != 5 || self.max_guesses != 6 {
+            return Err(format!(
+                "the challenge is {} letters in {} guesses, only 5 letters in 6 guesses can be played!",
+                self.answer.len(),
+                self.max_guesses
+            ).into());
+        }
+        if self.list_id != server.final_list_id() || !server.is_final(&self.answer) {
+            return Err("the challenge is made with another final word list!".into());
+        }
+        Ok(())
+    }
+}
+
+/// 32-bit FNV-1a hash
+pub fn fnv1a(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0x811c9dc5u32, |hash, x| (hash ^ *x as u32).wrapping_mul(0x01000193))
+}
+
+/// xor with a fixed stream, so the answer can't be read from the code, applying it twice gives back the bytes
+fn mask(bytes: &mut [u8]) {
+    let mut state: u32 = 0x5eed;
+    for byte in bytes {
+        state = state.wrapping_mul(1103515245).wrapping_add(12345);
+        *byte ^= (state >> 16) as u8;
+    }
+}
+
+fn base32_encode(bytes: &[u8]) -> String {
+    let mut code = String::new();
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    for byte in bytes {
+        buffer = buffer << 8 | *byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            code.push(ALPHABET[(buffer >> bits & 0x1f) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        code.push(ALPHABET[(buffer << (5 - bits) & 0x1f) as usize] as char);
+    }
+    code
+}
+
+fn base32_decode(code: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::new();
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    for letter in code.bytes() {
+        let value = ALPHABET.iter().position(|x| *x == letter)? as u32;
+        buffer = buffer << 5 | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits & 0xff) as u8);
+        }
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn crane() -> Challenge {
+        Challenge { answer: "CRANE".to_string(), list_id: 0xbeef, max_guesses: 6, difficult: true }
+    }
+
+    #[test]
+    fn round_trip() {
+        let code = crane().encode();
+        assert!(!code.contains("CRANE"));
+        let challenge = Challenge::decode(&code).unwrap();
+        assert_eq!(challenge.answer, "CRANE");
+        assert_eq!(challenge.list_id, 0xbeef);
+        assert_eq!(challenge.max_guesses, 6);
+        assert!(challenge.difficult);
+        // dashes, case and the letters read as digits don't matter
+        let loose = code.replace('-', "").to_lowercase().replace('0', "o").replace('1', "l");
+        assert_eq!(Challenge::decode(&loose).unwrap().answer, "CRANE");
+    }
+
+    #[test]
+    fn checksum_rejects_changed_code() {
+        let code = crane().encode();
+        for index in (0..code.len()).filter(|x| code.as_bytes()[*x] != b'-') {
+            let mut changed = code.clone().into_bytes();
+            changed[index] = if changed[index] == b'Z' { b'Y' } else { b'Z' };
+            assert!(Challenge::decode(&String::from_utf8(changed).unwrap()).is_err());
+        }
+    }
+
+    #[test]
+    fn version_and_length_are_checked() {
+        let mut bytes = base32_decode(&crane().encode().replace('-', "")).unwrap();
+        bytes[0] = VERSION + 1;
+        assert!(Challenge::decode(&base32_encode(&bytes)).is_err());
+        assert!(Challenge::decode(&base32_encode(&[VERSION, 0, 0])).is_err());
+        assert!(Challenge::decode("not a code").is_err());
+    }
+}
@@ -1,9 +1,11 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use std::path::Path;
 use std::fs::File;
 use std::io::Read;
 use serde::{Deserialize, Serialize};
 
+use crate::candidates;
+
 /// the Cli struct is for command lines args
 #[derive(Parser, Serialize, Deserialize)]
 #[command(name = "Wordle")]
@@ -44,6 +46,74 @@ pub struct Cli {
     /// give n prompt word 
     #[arg(short, long)]
     pub prompt: Option<i32>,
+    /// statistics json
+    #[arg(long = "stats-json")]
+    pub stats_json: Option<String>,
+    /// share result grid to stdout, clipboard or a file
+    #[arg(long)]
+    pub share: Option<String>,
+    /// ui theme: default, high-contrast, monochrome or a theme json
+    #[arg(long)]
+    pub theme: Option<String>,
+    /// animation of tiles
+    #[arg(long)]
+    pub animate: bool,
+    /// pause the game after n seconds without input
+    #[arg(long = "idle-timeout")]
+    pub idle_timeout: Option<u64>,
+    /// time limit of each puzzle in seconds
+    #[arg(long)]
+    pub timed: Option<u64>,
+    /// solve n puzzles in a row as fast as possible, implies random mode
+    #[arg(long)]
+    pub speedrun: Option<usize>,
+    /// daily puzzle of today, implies random mode
+    #[arg(long)]
+    pub daily: bool,
+    /// date of the daily puzzle instead of today, YYYY-MM-DD
+    #[arg(long)]
+    pub date: Option<String>,
+    /// date of the first daily puzzle, YYYY-MM-DD
+    #[arg(long)]
+    pub epoch: Option<String>,
+    /// play a challenge code made by `wordle challenge WORD`
+    #[arg(long)]
+    pub challenge: Option<String>,
+    /// lying mode, one tile of each row is false
+    #[arg(long)]
+    pub fibble: bool,
+    /// endless puzzles in the order of random mode, the winning row is carried to the first row of the next puzzle
+    #[arg(long)]
+    pub survival: bool,
+    /// practice games with undo, hints and the answer on demand, not counted in statistics
+    #[arg(long)]
+    pub practice: bool,
+    /// evil host: the answer is switched among the words consistent with the feedback until k guesses
+    #[arg(long)]
+    pub evil: Option<usize>,
+    /// panel of letter frequencies by position among the possible answers
+    #[arg(long = "heat-map")]
+    pub heat_map: bool,
+    /// panel of the possible answers sorted by alpha or score, and the number of them
+    #[arg(long)]
+    pub candidates: Option<String>,
+    #[command(subcommand)]
+    #[serde(skip)]
+    pub command: Option<Command>,
+}
+
+/// sub commands of wordle
+#[derive(Subcommand, Clone)]
+pub enum Command {
+    /// replay games recorded in the state json
+    Replay {
+        /// nth game to replay, default is the last game
+        game: Option<usize>,
+    },
+    /// make a challenge code of a word from the final word list, with -D for hard mode
+    Challenge {
+        word: String,
+    },
 }
 
 #[derive(Serialize, Deserialize)]
@@ -70,6 +140,38 @@ struct Config {
     // config: Option<String>,
     /// give n prompt word 
     prompt: Option<i32>,
+    /// statistics json
+    stats_json: Option<String>,
+    /// share result grid to stdout, clipboard or a file
+    share: Option<String>,
+    /// ui theme: default, high-contrast, monochrome or a theme json
+    theme: Option<String>,
+    /// animation of tiles
+    animate: Option<bool>,
+    /// pause the game after n seconds without input
+    idle_timeout: Option<u64>,
+    /// time limit of each puzzle in seconds
+    timed: Option<u64>,
+    /// solve n puzzles in a row as fast as possible, implies random mode
+    speedrun: Option<usize>,
+    /// daily puzzle of today, implies random mode
+    daily: Option<bool>,
+    /// date of the first daily puzzle, YYYY-MM-DD
+    epoch: Option<String>,
+    /// play a challenge code made by `wordle challenge WORD`
+    challenge: Option<String>,
+    /// lying mode, one tile of each row is false
+    fibble: Option<bool>,
+    /// endless puzzles in the order of random mode, the winning row is carried to the first row of the next puzzle
+    survival: Option<bool>,
+    /// practice games with undo, hints and the answer on demand, not counted in statistics
+    practice: Option<bool>,
+    /// evil host: the answer is switched among the words consistent with the feedback until k guesses
+    evil: Option<usize>,
+    /// panel of letter frequencies by position among the possible answers
+    heat_map: Option<bool>,
+    /// panel of the possible answers sorted by alpha or score, and the number of them
+    candidates: Option<String>,
 }
 
 impl Cli{
@@ -186,6 +288,102 @@ impl Cli{
                     },
                 }
 
+                if self.stats_json.is_none() {
+                    if let Some(stats_json) = &cli_config.stats_json {
+                        self.stats_json = Some(stats_json.to_string());
+                    }
+                }
+
+                if self.share.is_none() {
+                    if let Some(share) = &cli_config.share {
+                        self.share = Some(share.to_string());
+                    }
+                }
+
+                if self.theme.is_none() {
+                    if let Some(theme) = &cli_config.theme {
+                        self.theme = Some(theme.to_string());
+                    }
+                }
+
+                if !self.animate {
+                    if let Some(animate) = &cli_config.animate {
+                        self.animate = *animate;
+                    }
+                }
+
+                if self.idle_timeout.is_none() {
+                    if let Some(idle_timeout) = &cli_config.idle_timeout {
+                        self.idle_timeout = Some(*idle_timeout);
+                    }
+                }
+
+                if self.timed.is_none() {
+                    if let Some(timed) = &cli_config.timed {
+                        self.timed = Some(*timed);
+                    }
+                }
+
+                if self.speedrun.is_none() {
+                    if let Some(speedrun) = &cli_config.speedrun {
+                        self.speedrun = Some(*speedrun);
+                    }
+                }
+
+                if !self.daily {
+                    if let Some(daily) = &cli_config.daily {
+                        self.daily = *daily;
+                    }
+                }
+
+                if self.epoch.is_none() {
+                    if let Some(epoch) = &cli_config.epoch {
+                        self.epoch = Some(epoch.to_string());
+                    }
+                }
+
+                if self.challenge.is_none() {
+                    if let Some(challenge) = &cli_config.challenge {
+                        self.challenge = Some(challenge.to_string());
+                    }
+                }
+
+                if !self.fibble {
+                    if let Some(fibble) = &cli_config.fibble {
+                        self.fibble = *fibble;
+                    }
+                }
+
+                if !self.survival {
+                    if let Some(survival) = &cli_config.survival {
+                        self.survival = *survival;
+                    }
+                }
+
+                if !self.practice {
+                    if let Some(practice) = &cli_config.practice {
+                        self.practice = *practice;
+                    }
+                }
+
+                if self.evil.is_none() {
+                    if let Some(evil) = &cli_config.evil {
+                        self.evil = Some(*evil);
+                    }
+                }
+
+                if !self.heat_map {
+                    if let Some(heat_map) = &cli_config.heat_map {
+                        self.heat_map = *heat_map;
+                    }
+                }
+
+                if self.candidates.is_none() {
+                    if let Some(candidates) = &cli_config.candidates {
+                        self.candidates = Some(candidates.to_string());
+                    }
+                }
+
             }
             None => ()
 
@@ -198,6 +396,21 @@ impl Cli{
 
     /// check all args which have conflict
     fn check_conflict(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        // the answer of a challenge is given by the code
+        if self.challenge.is_some() && (self.word.is_some() || self.random || self.daily || self.speedrun.is_some() || self.survival) {
+            return Err("Challenge can't be used with word mode, random mode, daily mode, speedrun or survival!".into());
+        }
+
+        // the day of the daily puzzle is given by the date
+        if self.daily {
+            if self.word.is_some() || self.day.is_some() || self.speedrun.is_some() || self.survival {
+                return Err("Daily mode can't be used with word mode, -d/--day, speedrun or survival!".into());
+            }
+            self.random = true;
+        } else if self.date.is_some() {
+            return Err("--date is only valid in daily mode!".into());
+        }
+
         //In word mode, you con't use -d/--day or -s/--seed!
         match &self.word {
             Some(_word) => {
@@ -218,6 +431,32 @@ impl Cli{
             None => self.seed = Some(42),
         }
 
+        // speedrun plays puzzles in the order of random mode
+        if self.speedrun.is_some() {
+            if self.word.is_some() {
+                return Err("Speedrun and word mode can't exist at the same time!".into());
+            }
+            self.random = true;
+        }
+
+        // survival plays puzzles in the order of random mode, one run at a time
+        if self.survival {
+            if self.word.is_some() || self.speedrun.is_some() {
+                return Err("Survival can't be used with word mode or speedrun!".into());
+            }
+            self.random = true;
+        }
+
+        // practice games are not counted, so they can't be part of a counted run
+        if self.practice {
+            if self.daily || self.speedrun.is_some() || self.survival {
+                return Err("Practice can't be used with daily mode, speedrun or survival!".into());
+            }
+            if self.word.is_none() && self.challenge.is_none() {
+                self.random = true;
+            }
+        }
+
         //Random mode and word mode can't exist at the same time
         if self.random {
             if self.word.is_some() {
@@ -232,6 +471,33 @@ impl Cli{
             },
             None => (),
         }
+        if self.idle_timeout == Some(0) {
+            return Err("idle timeout less than 1 second".into());
+        }
+        if self.timed == Some(0) {
+            return Err("time limit less than 1 second".into());
+        }
+        // hard mode checks the next guess with the feedback, which may lie
+        if self.fibble && self.difficult {
+            return Err("Lying mode and hard mode can't exist at the same time!".into());
+        }
+        // the evil host picks answers by the true feedback, and the daily answer is the same for everyone
+        if self.evil.is_some() && (self.fibble || self.daily) {
+            return Err("Evil host can't be used with lying mode or daily mode!".into());
+        }
+        // carried greens and practice assists are given by an answer the evil host may switch
+        if self.evil.is_some() && (self.survival || self.practice) {
+            return Err("Evil host can't be used with survival mode or practice mode!".into());
+        }
+        if self.evil == Some(0) {
+            return Err("evil host committed before 1 guess".into());
+        }
+        if let Some(sort) = &self.candidates {
+            candidates::parse_sort(sort)?;
+        }
+        if self.speedrun == Some(0) {
+            return Err("speedrun of 0 puzzles".into());
+        }
         Ok(())
     } 
     
@@ -0,0 +1,54 @@
+use chrono::{DateTime, Duration, Local, NaiveDate, TimeZone};
+
+use crate::App;
+use crate::cli::Cli;
+use crate::player::Player;
+use crate::server::Server;
+
+/// date of daily puzzle 1 if --epoch is not given
+const DEFAULT_EPOCH: &str = "2021-06-19";
+
+fn parse_date(date: &str) -> Result<NaiveDate, Box<dyn std::error::Error>> {
+    match NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+        Ok(date) => Ok(date),
+        Err(_) => Err(format!("invalid date {}, should be YYYY-MM-DD!", date).into()),
+    }
+}
+
+/// date of the daily puzzle, today or --date
+pub fn puzzle_date(cli: &Cli) -> Result<NaiveDate, Box<dyn std::error::Error>> {
+    match &cli.date {
+        Some(date) => parse_date(date),
+        None => Ok(Local::now().date_naive()),
+    }
+}
+
+/// day of the daily puzzle counted from the epoch, wrapping around the final words
+pub fn puzzle_day(cli: &Cli, final_count: usize) -> Result<i32, Box<dyn std::error::Error>> {
+    let epoch = parse_date(cli.epoch.as_deref().unwrap_or(DEFAULT_EPOCH))?;
+    let days = (puzzle_date(cli)? - epoch).num_days();
+    if days < 0 {
+        return Err("the daily puzzle date is before the epoch!".into());
+    }
+    Ok((days % final_count as i64) as i32 + 1)
+}
+
+/// start of the next daily puzzle, None if the date is given by --date
+pub fn next_puzzle(cli: &Cli) -> Option<DateTime<Local>> {
+    if cli.date.is_some() {
+        return None;
+    }
+    let tomorrow = Local::now().date_naive() + Duration::days(1);
+    Local.from_local_datetime(&tomorrow.and_hms_opt(0, 0, 0)?).earliest()
+}
+
+/// set the day of the daily puzzle, false if it is already played in the state json
+pub fn start(cli: &Cli, server: &mut Server, player: &Player, app: &mut App) -> Result<bool, Box<dyn std::error::Error>> {
+    let date = puzzle_date(cli)?.to_string();
+    if player.games.iter().any(|game| game.date.as_ref() == Some(&date)) {
+        app.message = format!("The daily puzzle of {} is already played.\nCome back for the next one!", date);
+        return Ok(false);
+    }
+    server.set_day(puzzle_day(cli, server.final_count())?);
+    Ok(true)
+}
@@ -0,0 +1,109 @@
+use std::{fmt, io::{self, Stdout}, panic};
+use crossterm::{
+    cursor::Show,
+    event::{DisableMouseCapture, EnableMouseCapture, KeyCode},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use tui::{
+    backend::{Backend, CrosstermBackend},
+    layout::Alignment,
+    style::{Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame, Terminal,
+};
+
+use crate::{layout, next_key, ui, App, Overlay};
+
+/// error returned when the player quits, `main` exits cleanly on it
+#[derive(Debug)]
+pub struct Quit;
+
+impl fmt::Display for Quit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Quit.")
+    }
+}
+
+impl std::error::Error for Quit {}
+
+/// terminal in raw mode and alternate screen, restored on drop and on panic
+pub struct TerminalGuard {
+    pub terminal: Terminal<CrosstermBackend<Stdout>>,
+}
+
+impl TerminalGuard {
+    pub fn new() -> Result<TerminalGuard, Box<dyn std::error::Error>> {
+        // restore before the panic message is printed, or it is lost in the alternate screen
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            restore();
+            default_hook(info);
+        }));
+
+        enable_raw_mode()?;
+        let mut stdout = io::stdout();
+        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+        let terminal = Terminal::new(CrosstermBackend::new(stdout))?;
+        Ok(TerminalGuard { terminal })
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        restore();
+    }
+}
+
+/// come back terminal, errors are ignored as there is nothing more to do
+fn restore() {
+    let _ = disable_raw_mode();
+    let _ = execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture, Show);
+}
+
+/// ask the player before quitting, return `Quit` if confirmed
+pub fn confirm_quit<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Result<(), Box<dyn std::error::Error>> {
+    let overlay = app.overlay;
+    app.overlay = Some(Overlay::ConfirmQuit);
+    terminal.draw(|f| ui(f, app))?;
+    let key = next_key(terminal, app)?;
+    app.overlay = overlay;
+    terminal.draw(|f| ui(f, app))?;
+    match key {
+        KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => Err(Box::new(Quit)),
+        _ => Ok(()),
+    }
+}
+
+/// popup asking to confirm quitting
+pub fn draw_confirm_quit<B: Backend>(f: &mut Frame<B>, app: &App) {
+    let area = layout::centered_rect(40, 5, f.size());
+    let text = vec![
+        Spans::from(Span::styled("Quit the game?", Style::default().add_modifier(Modifier::BOLD))),
+        Spans::from(""),
+        Spans::from("Y/Enter: quit    any other key: back"),
+    ];
+    let paragraph = Paragraph::new(text)
+        .style(app.theme.message_style())
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL).title("Quit"));
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
+
+/// full screen shown while paused, the board is hidden
+pub fn draw_paused<B: Backend>(f: &mut Frame<B>, app: &App) {
+    let text = vec![
+        Spans::from(Span::styled("Paused", Style::default().add_modifier(Modifier::BOLD))),
+        Spans::from(""),
+        Spans::from("Press any key to continue"),
+    ];
+    let area = f.size();
+    let paragraph = Paragraph::new(text)
+        .style(app.theme.message_style())
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL).title("Wordle"));
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, layout::centered_rect(area.width, 5, area));
+}
@@ -0,0 +1,118 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{challenge, get_word_state};
+use crate::server::word_state_index;
+
+const STATES: [u8; 3] = [b'R', b'Y', b'G'];
+
+/// lies that can be told about `word_state`, (position, false state), never all G as that ends the game
+fn possible_lies(word_state: &[u8]) -> Vec<(usize, u8)> {
+    let mut lies = Vec::new();
+    for (index, state) in word_state.iter().enumerate() {
+        for false_state in STATES {
+            if false_state == *state {
+                continue;
+            }
+            if false_state == b'G' && word_state.iter().enumerate().all(|(i, x)| i == index || *x == b'G') {
+                continue;
+            }
+            lies.push((index, false_state));
+        }
+    }
+    lies
+}
+
+/// falsify one tile of the word state of `row`, the same for the same seed, answer and row,
+/// a correct guess is not falsified
+pub fn lie(word_state: &mut [u8], seed: u64, answer: &str, row: usize) {
+    if word_state.iter().all(|x| *x == b'G') {
+        return;
+    }
+    let key = challenge::fnv1a(format!("{}{}", answer, row).as_bytes()) as u64;
+    let mut rng = StdRng::seed_from_u64(seed ^ key);
+    let lies = possible_lies(word_state);
+    let (index, false_state) = lies[rng.gen_range(0..lies.len())];
+    word_state[index] = false_state;
+}
+
+/// `word` can be the answer if exactly one tile of the shown word state is false
+pub fn word_lying_check(word: &String, word_state: &[u8], guess_word: &String) -> bool {
+    let true_state = get_word_state(word, guess_word);
+    if true_state.iter().all(|x| *x == b'G') {
+        return word_state.iter().all(|x| *x == b'G');
+    }
+    true_state.iter().zip(word_state).filter(|(x, y)| x != y).count() == 1
+}
+
+/// spread each count of true word states over the states that can be shown,
+/// every lie is equally likely, so the weights of one true state add up to 90
+pub fn lying_pattern_counts(true_counts: &[i32]) -> Vec<i32> {
+    let mut counts = vec![0; true_counts.len()];
+    for (index, count) in true_counts.iter().enumerate() {
+        if *count == 0 {
+            continue;
+        }
+        let word_state = index_word_state(index);
+        if word_state.iter().all(|x| *x == b'G') {
+            counts[index] += count * 90;
+            continue;
+        }
+        let lies = possible_lies(&word_state);
+        for (position, false_state) in &lies {
+            let mut shown = word_state.clone();
+            shown[*position] = *false_state;
+            counts[word_state_index(&shown)] += count * 90 / lies.len() as i32;
+        }
+    }
+    counts
+}
+
+/// index in 0..243 => word state
+fn index_word_state(mut index: usize) -> Vec<u8> {
+    let mut word_state = vec![b'R'; 5];
+    for state in word_state.iter_mut().rev() {
+        *state = STATES[index % 3];
+        index /= 3;
+    }
+    word_state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANSWERS: [&str; 4] = ["CRANE", "SPOOK", "EERIE", "QUIET"];
+    const GUESSES: [&str; 5] = ["SLATE", "OOZES", "RECAP", "TUQUE", "NACRE"];
+
+    #[test]
+    fn one_tile_lies_and_the_answer_passes() {
+        for answer in ANSWERS {
+            for guess in GUESSES {
+                let (answer, guess) = (answer.to_string(), guess.to_string());
+                for row in 0..6 {
+                    let true_state = get_word_state(&answer, &guess);
+                    let mut word_state = true_state.clone();
+                    lie(&mut word_state, 42, &answer, row);
+                    assert_eq!(true_state.iter().zip(&word_state).filter(|(x, y)| x != y).count(), 1);
+                    assert!(word_state.iter().any(|x| *x != b'G'));
+                    assert!(word_lying_check(&answer, &word_state, &guess));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn correct_guess_is_not_falsified() {
+        let mut word_state = vec![b'G'; 5];
+        lie(&mut word_state, 42, "CRANE", 0);
+        assert_eq!(word_state, vec![b'G'; 5]);
+        assert!(word_lying_check(&"CRANE".to_string(), &word_state, &"CRANE".to_string()));
+    }
+
+    #[test]
+    fn lying_counts_keep_the_total() {
+        let true_counts: Vec<i32> = (0..243).map(|x| x % 7).collect();
+        let total: i32 = true_counts.iter().sum();
+        assert_eq!(lying_pattern_counts(&true_counts).iter().sum::<i32>(), total * 90);
+    }
+}
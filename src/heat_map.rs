@@ -0,0 +1,94 @@
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    style::{Color, Style},
+    text::{Span, Spans},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use crate::App;
+use crate::cli::Cli;
+use crate::server::Server;
+
+/// width of a percentage cell
+const CELL_WIDTH: usize = 4;
+/// width of a letter row, the letter and a cell for each position and for any position
+const ROW_WIDTH: usize = 1 + CELL_WIDTH * 6;
+
+/// letter frequencies among the possible answers
+pub struct HeatMap {
+    /// number of possible answers
+    pub total: usize,
+    /// positions[i][letter] is the number of possible answers with the letter at position i
+    pub positions: Vec<Vec<i32>>,
+    /// letters[letter] is the number of possible answers with the letter at any position
+    pub letters: Vec<i32>,
+}
+
+/// count letters of the possible answers after the guesses
+pub fn compute(server: &mut Server, word_states: &Vec<Vec<u8>>, guess_words: &Vec<String>) -> HeatMap {
+    server.get_all_possible_answers(word_states, guess_words);
+    let mut positions = vec![vec![0; 26]; 5];
+    let mut letters = vec![0; 26];
+    for word in &server.possible_answer {
+        let mut seen = [false; 26];
+        for (position, letter) in word.bytes().enumerate().take(5) {
+            let index = (letter - b'A') as usize;
+            positions[position][index] += 1;
+            seen[index] = true;
+        }
+        for (index, _seen) in seen.iter().enumerate().filter(|(_index, seen)| **seen) {
+            letters[index] += 1;
+        }
+    }
+    HeatMap { total: server.possible_answer.len(), positions, letters }
+}
+
+/// refresh the heat map shown in the game if --heat-map is given
+pub fn update(cli: &Cli, server: &mut Server, word_states: &Vec<Vec<u8>>, guess_words: &Vec<String>, app: &mut App) {
+    app.heat_map = if cli.heat_map { Some(compute(server, word_states, guess_words)) } else { None };
+}
+
+/// one cell of a letter row, the percentage of possible answers, `·` if there is none
+fn cell(count: i32, total: usize, color: Color) -> Span<'static> {
+    if count == 0 {
+        return Span::raw(format!("{:>width$}", "·", width = CELL_WIDTH));
+    }
+    let percent = count * 100 / total.max(1) as i32;
+    Span::styled(format!("{:>width$}", percent, width = CELL_WIDTH), Style::default().fg(color))
+}
+
+/// heat map panel, a row for each letter of the possible answers from the most common,
+/// with the percentage of possible answers having the letter at each position and at any position
+pub fn draw_heat_map<B: Backend>(f: &mut Frame<B>, heat_map: &HeatMap, app: &App, area: Rect) {
+    let block = Block::default().borders(Borders::ALL).title(format!("Heat Map: {} answers, %", heat_map.total));
+    let inner = block.inner(area);
+    let mut text = Vec::new();
+    if (inner.width as usize) < ROW_WIDTH {
+        text.push(Spans::from("Too narrow for the heat map"));
+    } else {
+        let header: String = ["1", "2", "3", "4", "5", "Any"].iter().map(|x| format!("{:>width$}", x, width = CELL_WIDTH)).collect();
+        text.push(Spans::from(format!(" {}", header)));
+
+        let mut letters: Vec<(usize, i32)> = heat_map.letters.iter().copied().enumerate().filter(|(_index, count)| *count > 0).collect();
+        letters.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        // the last line tells how many letters are left out if they don't fit
+        let rows = (inner.height as usize).saturating_sub(1);
+        let shown = if letters.len() > rows { rows.saturating_sub(1) } else { letters.len() };
+        for (index, count) in &letters[..shown] {
+            let mut spans = vec![Span::raw(((b'A' + *index as u8) as char).to_string())];
+            spans.extend(heat_map.positions.iter().map(|counts| cell(counts[*index], heat_map.total, app.theme.correct)));
+            spans.push(cell(*count, heat_map.total, app.theme.present));
+            text.push(Spans::from(spans));
+        }
+        if shown < letters.len() {
+            text.push(Spans::from(format!("+{} more letters", letters.len() - shown)));
+        }
+    }
+
+    let paragraph = Paragraph::new(text)
+        .style(app.theme.panel_style())
+        .block(block);
+    f.render_widget(paragraph, area);
+}
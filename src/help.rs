@@ -0,0 +1,107 @@
+use crossterm::event::KeyCode;
+use tui::{
+    backend::Backend,
+    style::{Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::{layout, App};
+use crate::cli::Cli;
+
+/// current settings shown in the help popup
+pub fn session_info(cli: &Cli) -> Vec<(String, String)> {
+    let mode = if cli.practice {
+        "practice"
+    } else if cli.daily {
+        "daily"
+    } else if cli.random {
+        "random"
+    } else if cli.challenge.is_some() {
+        "challenge"
+    } else if cli.word.is_some() {
+        "assigned word"
+    } else {
+        "two players"
+    };
+    let on_off = |x: bool| if x { "on".to_string() } else { "off".to_string() };
+    let or_default = |x: &Option<String>, default: &str| x.clone().unwrap_or(default.to_string());
+    vec![
+        ("Mode".to_string(), mode.to_string()),
+        ("Hard mode".to_string(), on_off(cli.difficult)),
+        ("Seed".to_string(), cli.seed.map(|x| x.to_string()).unwrap_or("-".to_string())),
+        ("Day".to_string(), cli.day.map(|x| x.to_string()).unwrap_or("-".to_string())),
+        ("Final words".to_string(), or_default(&cli.final_set, "builtin")),
+        ("Acceptable words".to_string(), or_default(&cli.acceptable_set, "builtin")),
+        ("Prompt words".to_string(), cli.prompt.map(|x| x.to_string()).unwrap_or("off".to_string())),
+        ("State file".to_string(), or_default(&cli.state, "none")),
+        ("Idle timeout".to_string(), cli.idle_timeout.map(|x| format!("{} s", x)).unwrap_or("off".to_string())),
+        ("Time limit".to_string(), cli.timed.map(|x| format!("{} s", x)).unwrap_or("off".to_string())),
+        ("Speedrun".to_string(), cli.speedrun.map(|x| format!("{} puzzles", x)).unwrap_or("off".to_string())),
+        ("Survival".to_string(), on_off(cli.survival)),
+        ("Evil host".to_string(), cli.evil.map(|x| format!("commits after {} guesses", x)).unwrap_or("off".to_string())),
+        ("Heat map".to_string(), on_off(cli.heat_map)),
+        ("Candidates".to_string(), or_default(&cli.candidates, "off")),
+        ("Lying tiles".to_string(), if cli.fibble { "one per row".to_string() } else { "off".to_string() }),
+    ]
+}
+
+/// popup of controls, colours, hard mode rules and settings
+pub fn draw_help<B: Backend>(f: &mut Frame<B>, app: &mut App) {
+    let title_style = Style::default().add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+    let tile = |letter: &'static str, state: u8| {
+        vec![Span::styled(format!(" {}{} ", letter, app.theme.state_symbol(state)), app.theme.tile_style(state)), Span::raw(" ")]
+    };
+
+    let mut text: Vec<Spans> = vec![
+        Spans::from(Span::styled("Controls", title_style)),
+        Spans::from("  A-Z: type a letter    Backspace: delete    Enter: submit"),
+        Spans::from("  Mouse: click keys on the keyboard panel"),
+        Spans::from("  Tab: statistics    F2: settings    F8: hint    ?/F1: help"),
+        Spans::from("  Esc: close popup / quit    Ctrl-C: quit without asking"),
+        Spans::from("  Candidates: Up/Down/PgUp/PgDn scroll    F9: sort A-Z or by score"),
+        Spans::from("  Practice: F3 undo  F4 letter  F5 candidates  F6 solver  F7 answer"),
+        Spans::from(""),
+        Spans::from(Span::styled("Colours", title_style)),
+        Spans::from([tile("G", b'G'), vec![Span::raw("letter in the answer at this position")]].concat()),
+        Spans::from([tile("Y", b'Y'), vec![Span::raw("letter in the answer at another position")]].concat()),
+        Spans::from([tile("R", b'R'), vec![Span::raw("letter not in the answer, or no more copies of it")]].concat()),
+        Spans::from(""),
+        Spans::from(Span::styled("Hard mode", title_style)),
+        Spans::from("  Compared with your last guess only:"),
+        Spans::from("  - green letters must stay at the same position"),
+        Spans::from("  - yellow letters must be used again, even at the same position"),
+        Spans::from("  - red letters can be used again"),
+        Spans::from(""),
+        Spans::from(Span::styled("Settings", title_style)),
+    ];
+    for (name, value) in &app.session_info {
+        text.push(Spans::from(format!("  {:<18}{}", name, value)));
+    }
+
+    let area = layout::centered_rect(72, text.len() as u16 + 2, f.size());
+    // lines wrapped in a narrow terminal take more rows
+    let width = area.width.saturating_sub(2).max(1) as usize;
+    let rows: usize = text.iter().map(|line| line.width().saturating_sub(1) / width + 1).sum();
+    let max_scroll = rows.saturating_sub(area.height.saturating_sub(2) as usize) as u16;
+    app.help_scroll = app.help_scroll.min(max_scroll);
+    let title = if max_scroll > 0 { "Help (Up/Down: scroll, Esc: close)" } else { "Help (Esc: close)" };
+    let paragraph = Paragraph::new(text)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .wrap(Wrap { trim: false })
+        .scroll((app.help_scroll, 0));
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
+
+/// scroll the help popup, it is clipped to the terminal
+pub fn handle_key(app: &mut App, code: KeyCode) {
+    app.help_scroll = match code {
+        KeyCode::Up => app.help_scroll.saturating_sub(1),
+        KeyCode::Down => app.help_scroll.saturating_add(1),
+        KeyCode::PageUp => app.help_scroll.saturating_sub(10),
+        KeyCode::PageDown => app.help_scroll.saturating_add(10),
+        _ => app.help_scroll,
+    };
+}
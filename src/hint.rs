@@ -0,0 +1,53 @@
+use crate::player::Game;
+use crate::server::Server;
+
+/// hints in the order they are given, each press of the hint key gives the next one
+pub const TIERS: [&str; 4] = ["candidates", "letter", "position", "best guess"];
+
+/// hint of `tier`, None if all tiers are given
+pub fn give(tier: usize, server: &mut Server, word_states: &Vec<Vec<u8>>, guess_words: &Vec<String>) -> Option<String> {
+    let answer = server.answer.to_uppercase();
+    match tier {
+        0 => {
+            server.get_all_possible_answers(word_states, guess_words);
+            Some(format!("{} possible answers", server.possible_answer.len()))
+        }
+        1 => {
+            // a letter of the answer not shown as G or Y yet
+            let found: Vec<u8> = guess_words
+                .iter()
+                .zip(word_states)
+                .flat_map(|(guess, word_state)| guess.bytes().zip(word_state.clone()))
+                .filter(|(_letter, state)| *state == b'G' || *state == b'Y')
+                .map(|(letter, _state)| letter)
+                .collect();
+            let letter = answer.bytes().find(|x| !found.contains(x)).unwrap_or(answer.as_bytes()[0]);
+            Some(format!("{} is in the answer", letter as char))
+        }
+        2 => {
+            // the first position not shown as G yet
+            let position = (0..5).find(|x| !word_states.iter().any(|word_state| word_state[*x] == b'G')).unwrap_or(0);
+            Some(format!("letter {} is {}", position + 1, answer.as_bytes()[position] as char))
+        }
+        3 => {
+            server.get_all_possible_answers(word_states, guess_words);
+            match server.best_guess() {
+                Some((word, _entropy)) => Some(format!("try {}", word)),
+                None => Some("too many possible answers for a suggestion".to_string()),
+            }
+        }
+        _ => None,
+    }
+}
+
+/// "n (candidates, letter...)" for the results of a game
+pub fn describe(hints: usize) -> String {
+    format!("{} ({})", hints, TIERS[..hints.min(TIERS.len())].join(", "))
+}
+
+/// number of games that used each tier
+pub fn tier_counts(games: &[Game]) -> Vec<i32> {
+    (0..TIERS.len())
+        .map(|tier| games.iter().filter(|game| game.hints > tier).count() as i32)
+        .collect()
+}
@@ -0,0 +1,83 @@
+use tui::layout::{Constraint, Direction, Layout, Rect};
+
+use crate::tiles::BOXED_HEIGHT;
+
+/// min terminal size for the game screen
+pub const MIN_WIDTH: u16 = 30;
+pub const MIN_HEIGHT: u16 = 16;
+
+/// terminal width to put extra panels on the right of the game
+const WIDE_WIDTH: u16 = 110;
+/// terminal width to put extra panels beside the keyboard
+const NORMAL_WIDTH: u16 = 60;
+/// terminal height to use a 4 lines message panel
+const NORMAL_HEIGHT: u16 = 22;
+
+/// areas of the game screen
+pub struct GameLayout {
+    pub message: Rect,
+    pub input: Rect,
+    pub keyboard: Rect,
+    /// extra panels like analysis and guess distribution, may be less than asked if there is no space
+    pub panels: Vec<Rect>,
+}
+
+pub fn too_small(size: Rect) -> bool {
+    size.width < MIN_WIDTH || size.height < MIN_HEIGHT
+}
+
+/// split the terminal for the game screen
+///
+/// wide terminal: extra panels in a column on the right
+/// normal terminal: extra panels beside the keyboard
+/// narrow terminal: no extra panels, and a smaller message panel if it is also short
+pub fn game_layout(size: Rect, panel_count: usize) -> GameLayout {
+    let message_height = if size.height < NORMAL_HEIGHT { 4 } else { 6 };
+    // boxed tiles if there is still enough space for the keyboard
+    let input_height = if size.height >= message_height + BOXED_HEIGHT + 7 { BOXED_HEIGHT } else { 8 };
+
+    let (game_area, right_area) = if panel_count > 0 && size.width >= WIDE_WIDTH {
+        let chunks = Layout::default()
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
+            .direction(Direction::Horizontal)
+            .split(size);
+        (chunks[0], Some(chunks[1]))
+    } else {
+        (size, None)
+    };
+
+    let chunks = Layout::default()
+        .constraints([Constraint::Length(message_height), Constraint::Length(input_height), Constraint::Min(5)].as_ref())
+        .direction(Direction::Vertical)
+        .split(game_area);
+    let mut keyboard = chunks[2];
+    let mut panels: Vec<Rect> = Vec::new();
+
+    if let Some(right_area) = right_area {
+        let constraints = vec![Constraint::Ratio(1, panel_count as u32); panel_count];
+        panels = Layout::default()
+            .constraints(constraints)
+            .direction(Direction::Vertical)
+            .split(right_area);
+    } else if panel_count > 0 && size.width >= NORMAL_WIDTH {
+        let mut constraints = vec![Constraint::Ratio(1, panel_count as u32 + 1); panel_count + 1];
+        if panel_count == 2 {
+            constraints = vec![Constraint::Percentage(25), Constraint::Percentage(50), Constraint::Percentage(25)];
+        }
+        let bottom_chunks = Layout::default()
+            .constraints(constraints)
+            .direction(Direction::Horizontal)
+            .split(chunks[2]);
+        keyboard = bottom_chunks[0];
+        panels = bottom_chunks[1..].to_vec();
+    }
+
+    GameLayout { message: chunks[0], input: chunks[1], keyboard, panels }
+}
+
+/// centered area for popups, `width` and `height` are clipped to `size`
+pub fn centered_rect(width: u16, height: u16, size: Rect) -> Rect {
+    let width = width.min(size.width);
+    let height = height.min(size.height);
+    Rect::new(size.x + (size.width - width) / 2, size.y + (size.height - height) / 2, width, height)
+}
@@ -1,17 +1,13 @@
-use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
-    execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
-};
+use crossterm::event::{self, Event, KeyCode, KeyModifiers, MouseButton, MouseEventKind};
 use tui::{
-    backend::{Backend, CrosstermBackend},
-    layout::{Alignment, Constraint, Direction, Layout},
-    style::{Color, Modifier, Style},
+    backend::Backend,
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
     text::{Span, Spans, Text},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{BarChart, Block, Borders, ListState, Paragraph},
     Frame, Terminal, 
 };
-use std::{io, time::Duration};
+use std::time::{Duration, Instant};
 use clap::Parser;
 use std::{collections::{HashMap, HashSet}};
 
@@ -20,39 +16,118 @@ mod builtin_words;
 mod player;
 use player::{Player, Game};
 mod cli;
-use cli::Cli;
+use cli::{Cli, Command};
 mod server;
 use server::Server;
+mod stats_view;
+mod replay;
+mod analysis;
+mod share;
+mod theme;
+use theme::Theme;
+mod layout;
+mod tiles;
+use tiles::Animation;
+mod help;
+mod settings;
+use settings::Settings;
+mod menu;
+use menu::MenuItem;
+mod exit;
+use exit::{Quit, TerminalGuard};
+mod timer;
+use timer::{Run, Speedrun, Timer, CLOCK_TICK};
+mod daily;
+mod challenge;
+use challenge::Challenge;
+mod fibble;
+mod survival;
+use survival::Survival;
+mod practice;
+mod hint;
+mod heat_map;
+use heat_map::HeatMap;
+mod candidates;
+use candidates::Candidates;
+use analysis::GameAnalysis;
 
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // raw mode
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
-    
+    // clap exits on --help and wrong args, before the terminal goes raw
+    let cli = Cli::parse();
+
+    // raw mode, the terminal comes back when the guard is dropped
+    let mut guard = TerminalGuard::new()?;
+
     // main logic
-    main_logic(&mut terminal)?;
+    let output = main_logic(cli, &mut guard.terminal)?;
 
-    // come back terminal
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
-    terminal.show_cursor()?;
+    // come back terminal before printing the output
+    drop(guard);
+    print!("{}", output);
 
     Ok(())
 }
 
+/// popup shown over the screen
+#[derive(PartialEq, Clone, Copy)]
+pub enum Overlay {
+    Help,
+    ConfirmQuit,
+    /// no key is pressed within the idle timeout
+    Paused,
+}
+
+/// which screen is shown in the terminal
+#[derive(PartialEq, Clone, Copy)]
+pub enum Screen {
+    Game,
+    Stats,
+    Settings,
+    Menu,
+}
+
 pub struct App {
     guess_words: Vec<String>,
     word_states: Vec<Vec<u8>>,
     message: String,
     alphabet_state: Vec<u8>,
+    guess_distribution: Vec<i32>,
+    screen: Screen,
+    games: Vec<Game>,
+    history_state: ListState,
+    analysis: Option<GameAnalysis>,
+    /// letter frequencies of the possible answers, --heat-map
+    heat_map: Option<HeatMap>,
+    /// possible answers shown in the game, --candidates
+    candidates: Option<Candidates>,
+    share_output: String,
+    theme: Theme,
+    /// area of every key on the keyboard panel, for mouse click
+    key_areas: Vec<(Rect, KeyCode)>,
+    animation: Option<Animation>,
+    overlay: Option<Overlay>,
+    /// settings shown in the help popup
+    session_info: Vec<(String, String)>,
+    /// first line shown in the help popup
+    help_scroll: u16,
+    settings: Settings,
+    /// config json to save settings to
+    config_path: Option<String>,
+    /// screen to go back to from statistics and settings
+    home: Screen,
+    /// pause the game if no key is pressed for this long
+    idle_timeout: Option<Duration>,
+    /// clock of the current game
+    timer: Option<Timer>,
+    /// speedrun in progress
+    run: Option<Run>,
+    /// survival run in progress
+    survival: Option<Survival>,
+    speedruns: Vec<Speedrun>,
+    menu_state: ListState,
+    /// count down to the next daily puzzle
+    next_daily: Option<chrono::DateTime<chrono::Local>>,
 }
 impl App {
     fn new() -> App {
@@ -61,217 +136,596 @@ impl App {
             word_states: (Vec::new()),
             message: ("Welcome to Wordle!\nPlease input word:".to_string()),
             alphabet_state: (vec!['X' as u8;26]),
+            guess_distribution: (Vec::new()),
+            screen: (Screen::Game),
+            games: (Vec::new()),
+            history_state: (ListState::default()),
+            analysis: (None),
+            heat_map: (None),
+            candidates: (None),
+            share_output: (String::new()),
+            theme: (Theme::default()),
+            key_areas: (Vec::new()),
+            animation: (None),
+            overlay: (None),
+            session_info: (Vec::new()),
+            help_scroll: (0),
+            settings: (Settings::new()),
+            config_path: (None),
+            home: (Screen::Game),
+            idle_timeout: (None),
+            timer: (None),
+            run: (None),
+            survival: (None),
+            speedruns: (Vec::new()),
+            menu_state: (ListState::default()),
+            next_daily: (None),
         }
     }
 }
 
 fn ui<B: Backend>(f: &mut Frame<B>, app: &mut App) {
+    match app.screen {
+        Screen::Game => draw_game(f, app),
+        Screen::Stats => stats_view::draw_stats(f, app),
+        Screen::Settings => settings::draw_settings(f, app),
+        Screen::Menu => menu::draw_menu(f, app),
+    }
+    match app.overlay {
+        Some(Overlay::Help) => help::draw_help(f, app),
+        Some(Overlay::ConfirmQuit) => exit::draw_confirm_quit(f, app),
+        Some(Overlay::Paused) => exit::draw_paused(f, app),
+        None => (),
+    }
+}
+
+fn draw_game<B: Backend>(f: &mut Frame<B>, app: &mut App) {
+    let size = f.size();
+    if layout::too_small(size) {
+        app.key_areas.clear();
+        let notice = format!(
+            "Terminal too small\n{}x{}, need at least {}x{}",
+            size.width, size.height, layout::MIN_WIDTH, layout::MIN_HEIGHT
+        );
+        let paragraph = Paragraph::new(notice)
+            .style(app.theme.message_style())
+            .alignment(Alignment::Center);
+        f.render_widget(paragraph, size);
+        return;
+    }
+
     // area
-    let chunks = Layout::default() // default
-        .constraints([Constraint::Length(6), Constraint::Length(8), Constraint::Min(5)].as_ref()) // 按照 3 行 和 最小 3 行的规则分割区域
-        .direction(Direction::Vertical) // vertical cutting
-        .split(f.size()); // segment Terminal area
+    let panel_count = app.analysis.is_some() as usize + !app.guess_distribution.is_empty() as usize + app.heat_map.is_some() as usize
+        + app.candidates.is_some() as usize;
+    let game_layout = layout::game_layout(size, panel_count);
 
     // message
     let paragraph = Paragraph::new(Text::styled(
         app.message.to_string(),
-        Style::default().add_modifier(Modifier::BOLD),
+        app.theme.message_style(),
     ))
     .block(Block::default().borders(Borders::ALL).title("Wordle"))
     .alignment(tui::layout::Alignment::Left);
 
-    f.render_widget(paragraph, chunks[0]);
+    f.render_widget(paragraph, game_layout.message);
+    timer::draw_clock(f, app, game_layout.message);
+    candidates::draw_remaining(f, app, game_layout.message);
 
     // input
-    let mut input_text = Vec::new();
-    for index in 0..app.guess_words.len() {
-        let word_ascii = app.guess_words[index].clone().into_bytes();
-        let mut word_char: Vec<char> = word_ascii.iter().map(|x| *x as char).collect();
-        while word_char.len() < 5 {
-            word_char.push('-');
-            app.word_states[index].push('X' as u8);
-        }
-
-        let mut word_span = Vec::new();
-        for letter in 0..5 {
-            match app.word_states[index][letter] {
-                71 => word_span.push(Span::styled(word_char[letter].to_string(), 
-                    Style::default().fg(Color::Green).add_modifier(Modifier::BOLD))),
-                89 => word_span.push(Span::styled(word_char[letter].to_string(), 
-                    Style::default().fg(Color::LightYellow).add_modifier(Modifier::BOLD))),
-                82 => word_span.push(Span::styled(word_char[letter].to_string(), 
-                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))),
-                _ => word_span.push(Span::styled(word_char[letter].to_string(),  
-                    Style::default().fg(Color::Gray).add_modifier(Modifier::BOLD))),
-            }
+    tiles::draw_tiles(f, app, game_layout.input);
+
+    // keyboard, analysis and guess distribution
+    draw_keyboard(f, app, game_layout.keyboard);
+    let mut panels = game_layout.panels.into_iter();
+    if let Some(analysis) = &app.analysis {
+        if let Some(area) = panels.next() {
+            analysis::draw_analysis(f, analysis, area);
         }
-        word_span.push("\n".into());
-        input_text.push(Spans::from(word_span));
-    }
-    let paragraph = Paragraph::new(Text::from(input_text))
-        .style(Style::default().bg(Color::White).fg(Color::Black))
-        .block(Block::default().borders(Borders::ALL).title("Input"))
-        .alignment(Alignment::Center);
-
-    f.render_widget(paragraph, chunks[1]);
-
-    // keyboard
-    let keyboard = vec!["QWERTYUIOP", "ASDFGHJKL", "ZXCVBNM"];
-    let mut keyboard_text = Vec::new();
-    for index in 0..3 {
-        let mut key_text =Vec::new();
-        for ch in keyboard[index].as_bytes() {
-            match app.alphabet_state[*ch as usize - 65] {
-                71 => key_text.push(Span::styled((*ch as char).to_string(),
-                  Style::default().fg(Color::Green).add_modifier(Modifier::BOLD))),
-                89 => key_text.push(Span::styled((*ch as char).to_string(),
-                  Style::default().fg(Color::LightYellow).add_modifier(Modifier::BOLD))),
-                82 => key_text.push(Span::styled((*ch as char).to_string(),
-                  Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))),
-                _ => key_text.push(Span::styled((*ch as char).to_string(), 
-                  Style::default().fg(Color::Gray).add_modifier(Modifier::BOLD))),
-            }
+    }
+    if !app.guess_distribution.is_empty() {
+        if let Some(area) = panels.next() {
+            draw_distribution(f, app, area);
+        }
+    }
+    if let Some(heat_map) = &app.heat_map {
+        if let Some(area) = panels.next() {
+            heat_map::draw_heat_map(f, heat_map, app, area);
         }
-        key_text.push("\n".into());
-        keyboard_text.push(Spans::from(key_text));
     }
-    let paragraph = Paragraph::new(Text::from(keyboard_text))
-    .style(Style::default().bg(Color::White).fg(Color::Black))
-    .block(Block::default().borders(Borders::ALL).title("Keyboard"))
-    .alignment(Alignment::Center);
+    if app.candidates.is_some() {
+        if let Some(area) = panels.next() {
+            candidates::draw_candidates(f, app, area);
+        }
+    }
+}
 
-    f.render_widget(paragraph, chunks[2]);
+/// guess distribution bar chart
+fn draw_distribution<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+    let labels: Vec<String> = (1..=app.guess_distribution.len()).map(|x| x.to_string()).collect();
+    let data: Vec<(&str, u64)> = labels
+        .iter()
+        .zip(&app.guess_distribution)
+        .map(|(label, count)| (label.as_str(), *count as u64))
+        .collect();
+    let barchart = BarChart::default()
+        .block(Block::default().borders(Borders::ALL).title("Guess Distribution"))
+        .data(&data)
+        .bar_width(3)
+        .bar_gap(1)
+        .bar_style(Style::default().fg(app.theme.correct))
+        .value_style(Style::default().fg(app.theme.foreground).bg(app.theme.correct).add_modifier(Modifier::BOLD));
+    f.render_widget(barchart, area);
+}
 
+/// keyboard panel, every key is rendered in its own area so that it can be clicked
+fn draw_keyboard<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
+    let block = Block::default().borders(Borders::ALL).title("Keyboard").style(app.theme.panel_style());
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    app.key_areas.clear();
+
+    // wide keys if the panel has enough space
+    let symbol_width = app.theme.state_symbol(b'X').len() as u16;
+    let wide = inner.width >= (symbol_width + 3) * 10;
+    let padding = if wide { " " } else { "" };
+    let (enter, backspace) = if wide { (" ENTER ", " ⌫ ") } else { ("↵", "⌫") };
+    let row_gap = if inner.height >= 5 { 2 } else { 1 };
+
+    let keyboard = ["QWERTYUIOP", "ASDFGHJKL", "ZXCVBNM"];
+    for (index, row) in keyboard.iter().enumerate() {
+        let mut keys: Vec<(Spans, KeyCode)> = Vec::new();
+        if index == 2 {
+            keys.push((Spans::from(enter), KeyCode::Enter));
+        }
+        for ch in row.chars() {
+            let state = app.alphabet_state[ch as usize - 65];
+            keys.push((Spans::from(vec![
+                Span::raw(padding),
+                Span::styled(ch.to_string(), app.theme.state_style(state)),
+                Span::raw(app.theme.state_symbol(state)),
+                Span::raw(padding),
+            ]), KeyCode::Char(ch)));
+        }
+        if index == 2 {
+            keys.push((Spans::from(backspace), KeyCode::Backspace));
+        }
+
+        let y = inner.y + index as u16 * row_gap;
+        let row_width: u16 = keys.iter().map(|(spans, _)| spans.width() as u16).sum();
+        if y >= inner.y + inner.height || row_width > inner.width {
+            continue;
+        }
+        let mut x = inner.x + (inner.width - row_width) / 2;
+        for (spans, code) in keys {
+            let key_area = Rect::new(x, y, spans.width() as u16, 1);
+            x += key_area.width;
+            f.render_widget(Paragraph::new(spans), key_area);
+            app.key_areas.push((key_area, code));
+        }
+    }
 }
 
-/// The main logic function for the Wordle game, implement your own logic here
+/// wait for the next key for the game, `KeyCode::Null` if the clock of a timed game runs out
 /// 
+/// keys for switching screens and keys on other screens are processed here,
+/// and the game is paused if no key is pressed within the idle timeout
+fn next_key<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Result<KeyCode, Box<dyn std::error::Error>> {
+    let mut idle_start = Instant::now();
+    // popup covered by the pause screen, shown again when the game resumes
+    let mut covered = None;
+    loop {
+        // redraw the clock and watch the idle timeout until there is an event
+        if (timer::clock_shown(app) || app.idle_timeout.is_some()) && !event::poll(CLOCK_TICK)? {
+            if app.overlay == Some(Overlay::Paused) {
+                continue;
+            }
+            if matches!(app.idle_timeout, Some(idle_timeout) if idle_start.elapsed() >= idle_timeout) {
+                covered = app.overlay.replace(Overlay::Paused);
+                if let Some(timer) = &mut app.timer {
+                    timer.pause();
+                }
+            } else if matches!(&app.timer, Some(timer) if timer.time_up()) {
+                return Ok(KeyCode::Null);
+            }
+            terminal.draw(|f| ui(f, app))?;
+            continue;
+        }
+        let event = event::read()?;
+        idle_start = Instant::now();
+        // click on the keyboard panel
+        if let Event::Mouse(mouse) = event {
+            if app.screen == Screen::Game && app.overlay.is_none() && mouse.kind == MouseEventKind::Down(MouseButton::Left) {
+                for (key_area, code) in &app.key_areas {
+                    if key_area.x <= mouse.column && mouse.column < key_area.x + key_area.width && key_area.y == mouse.row {
+                        return Ok(*code);
+                    }
+                }
+            }
+        }
+        if let Event::Resize(_, _) = event {
+            terminal.draw(|f| ui(f, app))?;
+        }
+        if let Event::Key(key) = event {
+            if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+                return Err(Box::new(Quit));
+            }
+            // any key resumes the paused game
+            if app.overlay == Some(Overlay::Paused) {
+                app.overlay = covered.take();
+                if let Some(timer) = &mut app.timer {
+                    timer.resume();
+                }
+                terminal.draw(|f| ui(f, app))?;
+                continue;
+            }
+            // the confirm popup is answered by the caller
+            if app.overlay == Some(Overlay::ConfirmQuit) {
+                return Ok(key.code);
+            }
+            // popup takes all keys
+            if app.overlay.is_some() {
+                if matches!(key.code, KeyCode::Esc | KeyCode::F(1) | KeyCode::Char('?')) {
+                    app.overlay = None;
+                } else {
+                    help::handle_key(app, key.code);
+                }
+                terminal.draw(|f| ui(f, app))?;
+                continue;
+            }
+            if matches!(key.code, KeyCode::F(1) | KeyCode::Char('?')) {
+                app.overlay = Some(Overlay::Help);
+                app.help_scroll = 0;
+                terminal.draw(|f| ui(f, app))?;
+                continue;
+            }
+            match app.screen {
+                Screen::Game | Screen::Menu => match key.code {
+                    KeyCode::Tab => app.screen = Screen::Stats,
+                    KeyCode::F(2) => app.screen = Screen::Settings,
+                    code => return Ok(code),
+                },
+                Screen::Stats => stats_view::handle_key(app, key.code),
+                Screen::Settings => settings::handle_key(app, key.code),
+            }
+            terminal.draw(|f| ui(f, app))?;
+        }
+    }
+}
+
+/// The main logic function for the Wordle game, implement your own logic here
 /// 
+/// return the output printed after the terminal is restored
 /// 
-fn main_logic<B: Backend>(terminal: &mut Terminal<B>) -> Result<(), Box<dyn std::error::Error>> {
+fn main_logic<B: Backend>(mut cli: Cli, terminal: &mut Terminal<B>) -> Result<String, Box<dyn std::error::Error>> {
 
-    let mut cli = Cli::parse();
     cli.mix_with_config()?;
     let mut player = Player::new();
     player.read_state_before(&cli)?;
     let mut server = Server::new(&cli);
     server.word_list_process(&cli)?;
     let mut app =App::new();
-    terminal.draw(|f| ui(f, &mut app))?;
+    app.theme = Theme::load(&cli.theme)?;
+    app.session_info = help::session_info(&cli);
+    app.games = player.games.clone();
+    app.settings = Settings::from_cli(&cli);
+    app.config_path = cli.config.clone();
+    if let Some(Command::Challenge { word }) = &cli.command {
+        let code = Challenge::new(word, &server, cli.difficult)?.encode();
+        return Ok(format!("Challenge code: {}\nPlay it with: wordle --challenge {}\n", code, code));
+    }
+    // the answer of a challenge is played like an assigned word
+    if let Some(code) = &cli.challenge {
+        let challenge = Challenge::decode(code)?;
+        challenge.check(&server)?;
+        if challenge.difficult && cli.fibble {
+            return Err("the challenge is in hard mode, it can't be played in lying mode!".into());
+        }
+        cli.difficult |= challenge.difficult;
+        cli.word = Some(challenge.answer);
+        // like custom words, challenge games are not saved in the state json
+        cli.state = None;
+        app.session_info = help::session_info(&cli);
+    }
+    app.idle_timeout = cli.idle_timeout.map(Duration::from_secs);
+    app.speedruns = player.speedruns.clone();
+
+    // quitting is not an error, the shared results are still printed
+    match run(&mut server, &mut player, &mut cli, terminal, &mut app) {
+        Err(error) if !error.is::<Quit>() => Err(error),
+        _ => Ok(app.share_output),
+    }
+}
+
+/// replay, play the mode given by the command line, or choose modes from the menu
+fn run<B: Backend>(server: &mut Server, player: &mut Player, cli: &mut Cli, terminal: &mut Terminal<B>, app: &mut App) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(Command::Replay { game }) = &cli.command {
+        return replay::replay_games(server, player, *game, terminal, app);
+    }
+    if !menu::enabled(cli) {
+        terminal.draw(|f| ui(f, app))?;
+        if cli.practice {
+            return play_practice(server, player, cli, false, terminal, app);
+        }
+        return play_session(server, player, cli, cli.daily, terminal, app);
+    }
+
+    app.message.clear();
+    loop {
+        match menu::choose(terminal, app)? {
+            MenuItem::Quit => return Ok(()),
+            MenuItem::Replay => {
+                if player.games.is_empty() {
+                    app.message = "No game to replay yet.".to_string();
+                } else {
+                    replay::replay_games(server, player, None, terminal, app)?;
+                    app.message.clear();
+                }
+            }
+            item => {
+                menu::start_mode(item, cli, server);
+                // the evil host only plays random and custom games
+                let evil = cli.evil.take();
+                if item == MenuItem::Random || item == MenuItem::Custom {
+                    cli.evil = evil;
+                }
+                app.session_info = help::session_info(cli);
+                // custom words are not saved in the state json
+                let state = cli.state.take();
+                if item != MenuItem::Custom {
+                    cli.state = state.clone();
+                }
+                if item == MenuItem::Practice {
+                    play_practice(server, player, cli, true, terminal, app)?;
+                } else {
+                    play_session(server, player, cli, item != MenuItem::Random, terminal, app)?;
+                }
+                cli.state = state;
+                cli.evil = evil;
+                app.games = player.games.clone();
+                app.message.clear();
+            }
+        }
+    }
+}
+
+/// practice games are played by a new player, they are not counted in the stats json
+/// and are saved apart from the games of the player in the state json
+fn play_practice<B: Backend>(server: &mut Server, player: &mut Player, cli: &mut Cli, single: bool, terminal: &mut Terminal<B>, app: &mut App) -> Result<(), Box<dyn std::error::Error>> {
+    let state = cli.state.take();
+    let stats_json = cli.stats_json.take();
+    let mut practice_player = Player::new();
+    let result = play_session(server, &mut practice_player, cli, single, terminal, app);
+    cli.state = state;
+    cli.stats_json = stats_json;
+    player.practice.append(&mut practice_player.games);
+    app.games = player.games.clone();
+    player.write_state_after(cli)?;
+    result
+}
+
+/// play games of one mode until the player stops, `single` plays only one game
+fn play_session<B: Backend>(server: &mut Server, player: &mut Player, cli: &mut Cli, single: bool, terminal: &mut Terminal<B>, app: &mut App) -> Result<(), Box<dyn std::error::Error>> {
     loop {
         // process other logic
-        server.init_secret_word(&cli, terminal, &mut app)?;
-        play_game(&mut server, &mut player, &cli, terminal, &mut app)?;
-        player.write_state_after(&cli)?;
-        if player.have_next_game(&cli, terminal, &mut app)? == false {
+        settings::apply(cli, server, app)?;
+        if let (Some(puzzles), None) = (cli.speedrun, &app.run) {
+            app.run = Some(Run::new(puzzles, server.next_day()));
+        }
+        if cli.survival && app.survival.is_none() {
+            app.survival = Some(Survival::default());
+        }
+        if cli.daily && !daily::start(cli, server, player, app)? {
+            wait_for_next_daily(cli, terminal, app)?;
+            break;
+        }
+        server.init_secret_word(cli, terminal, app)?;
+        play_game(server, player, cli, terminal, app)?;
+        timer::count_run_game(player, app);
+        survival::count_game(player, app);
+        player.write_state_after(cli)?;
+        player.write_stats_after(cli)?;
+        if single {
+            wait_for_next_daily(cli, terminal, app)?;
+            break;
+        }
+        // next puzzle of the speedrun, the clock keeps running
+        if app.run.is_some() {
+            continue;
+        }
+        // next puzzle of the survival run, after the solved board is seen
+        if app.survival.is_some() {
+            wait_for_next_daily(cli, terminal, app)?;
+            continue;
+        }
+        if !player.have_next_game(cli, terminal, app)? {
             break;
         }
     }
-
+    // save settings changed after the last game
+    settings::apply(cli, server, app)?;
     Ok(())
 }
 
 
 
 
+/// wait for a key after the last game, with a count down to the next daily puzzle
+fn wait_for_next_daily<B: Backend>(cli: &Cli, terminal: &mut Terminal<B>, app: &mut App) -> Result<(), Box<dyn std::error::Error>> {
+    if cli.daily {
+        app.next_daily = daily::next_puzzle(cli);
+    }
+    app.message += "\nPress any key to continue";
+    terminal.draw(|f| ui(f, app))?;
+    next_key(terminal, app)?;
+    app.next_daily = None;
+    Ok(())
+}
+
 /// play game to guess secret word, we can try 6 times
 fn play_game<B: Backend>(server: &mut Server, player: &mut Player, cli: &Cli, terminal: &mut Terminal<B>, app: &mut App) -> Result<(), Box<dyn std::error::Error>> 
 {
     app.alphabet_state = vec!['X' as u8; 26];
-    app.message = "Welcome to Wordle!\nRound ".to_string();
+    app.analysis = None;
+    app.message = "Welcome to Wordle! Press ? for help\nRound ".to_string();
     app.message += (player.total_rounds + 1).to_string().as_str();
+    if let Some(survival) = &app.survival {
+        app.message += &format!(", survival: {} solved, best run {}", survival.solved, player.best_survival);
+    }
     app.message += "\nPlease input word:";
+    if cli.practice {
+        app.message += &format!("\nPractice, not counted. {}", practice::CONTROLS);
+    }
     app.guess_words.clear();
     app.word_states.clear();
-    app.guess_words.push(String::new());
-    app.word_states.push(Vec::new());
-    terminal.draw(|f| ui(f, app))?;
-    app.word_states.pop();
-    app.guess_words.pop();
+    app.timer = Some(Timer::new(cli.timed.map(Duration::from_secs)));
 
     let mut guess_count = 0;
+    let mut times: Vec<u64> = Vec::new();
+    let date = if cli.daily { Some(daily::puzzle_date(cli)?.to_string()) } else { None };
+    let mut time_up = false;
+    let mut won = false;
+    let mut feedback: Vec<String> = Vec::new();
+    let mut assists: Vec<String> = Vec::new();
+    let mut hints: usize = 0;
+    let mut switches: Vec<String> = Vec::new();
     let mut word_states: Vec<Vec<u8>> = Vec::new();
     let mut guess_words: Vec<String> = Vec::new();
     let mut last_word_state: Vec<u8> = Vec::new();
     let mut last_guess_word: String = String::new();
     let mut alphabet_state: Vec<u8> = vec!['X' as u8; 26];
+    // green letters carried from the previous puzzle of a survival run, locked in the first row
+    let mut carried = survival::carried_greens(app);
+    if carried.iter().any(|x| x.is_some()) {
+        app.message += "\nThe first row starts with the last answer, press Enter";
+    }
+    app.candidates = None;
+    heat_map::update(cli, server, &word_states, &guess_words, app);
+    candidates::update(cli, server, &word_states, &guess_words, app);
     player.total_rounds += 1;
     //server.recommend_n_possible_answers(&word_states, &guess_words, &cli.prompt);
     while guess_count < 6 {
 
 
         let mut guess_word = String::new();
-        let mut word_state: Vec<u8> = Vec::new();
         // process keyboard input
         // block
-        while crossterm::event::poll(Duration::from_secs(60))?{
-            if let Event::Key(key) = event::read()? {
-                match key.code {
-                    KeyCode::Esc => {
-                        // come back terminal
-                        let backend = CrosstermBackend::new(io::stdout());
-                        let mut terminal = Terminal::new(backend)?;
-                        disable_raw_mode()?;
-                        execute!(
-                            terminal.backend_mut(),
-                            LeaveAlternateScreen,
-                            DisableMouseCapture
-                        )?;
-                        terminal.show_cursor()?;
-                        return Err("Force Quit.".into());
-                    }
-                    KeyCode::Char(ch) => {
-                        if guess_word.len() < 5 {
-                            guess_word.push(ch);
-                            word_state.push(88);
-                            app.guess_words.push(guess_word.clone());
-                            app.word_states.push(word_state.clone());
-                            terminal.draw(|f| ui(f, app))?;
-                            app.guess_words.pop();
-                            app.word_states.pop();
+        loop {
+            let (shown_word, shown_state) = survival::fill(&guess_word, &carried);
+            app.guess_words.push(shown_word);
+            app.word_states.push(shown_state);
+            terminal.draw(|f| ui(f, app))?;
+            let key = next_key(terminal, app)?;
+            app.guess_words.pop();
+            app.word_states.pop();
+            // scroll and sort of the candidate list
+            if candidates::handle_key(cli, server, &word_states, &guess_words, key, app) {
+                continue;
+            }
+
+            match key {
+                KeyCode::Enter => break,
+                KeyCode::Null => {
+                    time_up = true;
+                    break;
+                }
+                KeyCode::Esc => exit::confirm_quit(terminal, app)?,
+                KeyCode::Char(ch) if guess_word.len() < survival::free_slots(&carried) => guess_word.push(ch),
+                KeyCode::Backspace => {
+                    guess_word.pop();
+                }
+                // a hint would give away an answer the evil host may still switch
+                KeyCode::F(8) if cli.evil > Some(guess_count as usize) => {
+                    app.message = "No hints before the host commits\nPlease input word:".to_string();
+                }
+                KeyCode::F(8) => {
+                    match hint::give(hints, server, &word_states, &guess_words) {
+                        Some(hint) => {
+                            hints += 1;
+                            app.message = format!("Hint {}/{}: {}\nPlease input word:", hints, hint::TIERS.len(), hint);
                         }
-                    
+                        None => app.message = "No more hints\nPlease input word:".to_string(),
                     }
-                    KeyCode::Enter => {
-                        break;
-                    }
-                    KeyCode::Backspace => {
-                        guess_word.pop();
-                        word_state.pop();
-                        app.guess_words.push(guess_word.clone());
-                        app.word_states.push(word_state.clone());
-                        terminal.draw(|f| ui(f, app))?;
+                }
+                KeyCode::F(3) if cli.practice => {
+                    // undo the last guess, the keyboard is coloured again by the guesses left
+                    if let Some(guess) = guess_words.pop() {
+                        guess_count -= 1;
+                        word_states.pop();
+                        times.pop();
+                        feedback.truncate(guess_words.len());
                         app.guess_words.pop();
                         app.word_states.pop();
+                        alphabet_state = vec![b'X'; 26];
+                        for (guess, word_state) in guess_words.iter().zip(&word_states) {
+                            update_alphabet_state(guess, word_state, &mut alphabet_state);
+                        }
+                        app.alphabet_state = alphabet_state.clone();
+                        last_guess_word = guess_words.last().cloned().unwrap_or_default();
+                        last_word_state = word_states.last().cloned().unwrap_or_default();
+                        heat_map::update(cli, server, &word_states, &guess_words, app);
+                        candidates::update(cli, server, &word_states, &guess_words, app);
+                        app.message = format!("Undo {}\n{}", guess, practice::CONTROLS);
+                        assists.push(format!("undo {}", guess));
+                    }
+                }
+                key if cli.practice => {
+                    if let Some((message, assist)) = practice::assist(key, server, &word_states, &guess_words) {
+                        app.message = format!("{}\n{}", message, practice::CONTROLS);
+                        assists.push(assist);
                     }
-                    _ => {}
                 }
+                _ => {}
             }
         }
 
+        if time_up {
+            break;
+        }
+        guess_word = survival::fill(&guess_word, &carried).0;
         guess_word = guess_word.trim().to_string();
         guess_word.make_ascii_uppercase();
 
-        if server.is_valid(&guess_word, &last_word_state, &last_guess_word, cli.difficult) {
+        if server.is_valid(&guess_word, &last_word_state, &last_guess_word, cli.difficult && !cli.fibble) {
 
             guess_count += 1;
             guess_words.push(guess_word.clone());
+            carried = vec![None; 5];
+            times.push(app.timer.as_ref().map_or(0, |timer| timer.elapsed().as_millis() as u64));
 
             let word_count = player.hot_words.entry(guess_word.clone()).or_insert(0);
             *word_count += 1;
 
-            let mut word_state: Vec<u8> = vec!['R' as u8; 5];
-
-            let is_exit: bool = compare_secret_guess(&server.answer, &guess_word, &mut word_state, &mut alphabet_state); 
+            // the evil host may switch the answer until it commits after k guesses
+            if let Some(handicap) = cli.evil {
+                if guess_count as usize <= handicap {
+                    if let Some((old_answer, new_answer)) = server.evil_switch(&guess_word, &word_states, &guess_words) {
+                        switches.push(format!("{}: {} -> {}", guess_count, old_answer, new_answer));
+                    }
+                }
+            }
+            let mut word_state = get_word_state(&server.answer, &guess_word);
+            if cli.fibble {
+                fibble::lie(&mut word_state, cli.seed.unwrap(), &server.answer, guess_count as usize - 1);
+                feedback.push(String::from_utf8_lossy(&word_state).to_string());
+            }
+            let is_exit: bool = update_alphabet_state(&guess_word, &word_state, &mut alphabet_state);
 
             word_states.push(word_state.clone());
+            heat_map::update(cli, server, &word_states, &guess_words, app);
+            candidates::update(cli, server, &word_states, &guess_words, app);
 
-            app.alphabet_state = alphabet_state.clone();
             app.guess_words.push(guess_word.clone());
-            app.guess_words.push(String::new());
             app.word_states.push(word_state.clone());
+            if cli.animate {
+                tiles::animate(terminal, app, Animation::Reveal { row: app.guess_words.len() - 1, frame: 0 })?;
+            }
+            app.alphabet_state = alphabet_state.clone();
+            app.guess_words.push(String::new());
             app.word_states.push(Vec::new());
             app.message = "Word is Wrong\nPlease input word again:".to_string();
+            if cli.evil == Some(guess_count as usize) {
+                app.message += "\nThe host has committed to the answer";
+            }
             terminal.draw(|f| ui(f, app))?;
             
 
@@ -279,13 +733,8 @@ fn play_game<B: Backend>(server: &mut Server, player: &mut Player, cli: &Cli, te
                 app.word_states.pop();
                 app.guess_words.pop();
                 // if guess == secret, exit 
-                app.message = "CORRECT with times: ".to_string() + &guess_count.to_string();
-                terminal.draw(|f| ui(f, app))?;
-                // statistics
-                player.win_rounds +=1;
-                player.win_guess_times.push(guess_count);
-                player.games.push(Game{answer: (server.answer.clone()), guesses: (guess_words)});
-                return Ok(());
+                won = true;
+                break;
             }
             last_guess_word = guess_word;
             last_word_state = word_state;
@@ -295,6 +744,13 @@ fn play_game<B: Backend>(server: &mut Server, player: &mut Player, cli: &Cli, te
             app.guess_words.pop();
 
         }else {
+            if cli.animate {
+                app.guess_words.push(guess_word.clone());
+                app.word_states.push(Vec::new());
+                tiles::animate(terminal, app, Animation::Shake { row: app.guess_words.len() - 1, frame: 0 })?;
+                app.guess_words.pop();
+                app.word_states.pop();
+            }
             app.message = "Word is invalid\nPlease input word again:".to_string();
             app.word_states.push(Vec::new());
             app.guess_words.push(String::new());
@@ -306,10 +762,35 @@ fn play_game<B: Backend>(server: &mut Server, player: &mut Player, cli: &Cli, te
         }
     }
     
-    // failed!!!
-    app.message = "FAILED and answer is ".to_string() + &server.answer;
+    app.timer = None;
+    if won {
+        app.message = "CORRECT with times: ".to_string() + &guess_count.to_string();
+        // statistics
+        player.win_rounds +=1;
+        player.win_guess_times.push(guess_count);
+    } else if time_up {
+        // failed!!!
+        app.message = "TIME UP and answer is ".to_string() + &server.answer;
+    } else {
+        app.message = "FAILED and answer is ".to_string() + &server.answer;
+    }
+    if !assists.is_empty() {
+        app.message += &format!("\nAssistance: {}", assists.join(", "));
+    }
+    if hints > 0 {
+        app.message += &format!("\nHints used: {}", hint::describe(hints));
+    }
+    if !switches.is_empty() {
+        app.message += &format!("\nThe host switched the answer {} times", switches.len());
+    }
+    share::share_result(cli, server.day, &word_states, won, app)?;
+    terminal.draw(|f| ui(f, app))?;
+    player.games.push(Game{answer: (server.answer.clone()), guesses: (guess_words), difficult: (cli.difficult), times: (times), date: (date), feedback: (feedback), assists: (assists), hints: (hints), switches: (switches), committed: (cli.evil.map(|_| server.answer.clone()))});
+    if !cli.practice {
+        app.games = player.games.clone();
+    }
+    app.analysis = Some(analysis::analyze_game(server, &player.games[player.games.len() - 1]));
     terminal.draw(|f| ui(f, app))?;
-    player.games.push(Game{answer: (server.answer.clone()), guesses: (guess_words)});
     Ok(())
 
 }
@@ -319,8 +800,13 @@ fn play_game<B: Backend>(server: &mut Server, player: &mut Player, cli: &Cli, te
 
 pub fn compare_secret_guess(secret_word: &String, guess_word: &String, word_state: &mut Vec<u8>, alphabet_state: &mut Vec<u8>) -> bool {
     *word_state = get_word_state(secret_word, guess_word);
+    update_alphabet_state(guess_word, word_state, alphabet_state)
+}
+
+/// color the keyboard with the word state of a guess, true if all letters are G
+pub fn update_alphabet_state(guess_word: &str, word_state: &[u8], alphabet_state: &mut [u8]) -> bool {
     let mut count_equal = 0;
-    let guess_word_assci: Vec<u8> = guess_word.clone().into_bytes();
+    let guess_word_assci: Vec<u8> = guess_word.as_bytes().to_vec();
 
 
     for index in 0..5 {
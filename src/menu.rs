@@ -0,0 +1,151 @@
+use crossterm::event::KeyCode;
+use rand::Rng;
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::Spans,
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+    Frame, Terminal,
+};
+
+use crate::{next_key, ui, App, Screen};
+use crate::cli::Cli;
+use crate::server::Server;
+
+/// entries of the start menu
+#[derive(PartialEq, Clone, Copy)]
+pub enum MenuItem {
+    Daily,
+    Random,
+    Custom,
+    Practice,
+    Statistics,
+    Replay,
+    Quit,
+}
+
+const ITEMS: [MenuItem; 7] = [
+    MenuItem::Daily,
+    MenuItem::Random,
+    MenuItem::Custom,
+    MenuItem::Practice,
+    MenuItem::Statistics,
+    MenuItem::Replay,
+    MenuItem::Quit,
+];
+
+impl MenuItem {
+    fn label(&self) -> &'static str {
+        match self {
+            MenuItem::Daily => "Daily",
+            MenuItem::Random => "Random",
+            MenuItem::Custom => "Custom word",
+            MenuItem::Practice => "Practice",
+            MenuItem::Statistics => "Statistics",
+            MenuItem::Replay => "Replay",
+            MenuItem::Quit => "Quit",
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            MenuItem::Daily => "The puzzle of today, one game a day.",
+            MenuItem::Random => "Random puzzles one after another, saved to the state file.",
+            MenuItem::Custom => "Two players: one types the secret word, the other guesses it.",
+            MenuItem::Practice => "A random puzzle that is not counted in statistics, with undo, hints and the answer on demand.",
+            MenuItem::Statistics => "Statistics and history of the games played.",
+            MenuItem::Replay => "Replay the games played guess by guess.",
+            MenuItem::Quit => "Leave the game.",
+        }
+    }
+}
+
+/// the menu is shown if no mode is given by the command line or the config json
+pub fn enabled(cli: &Cli) -> bool {
+    !cli.random && cli.word.is_none() && cli.command.is_none()
+}
+
+/// show the start menu until a mode, replay or quit is chosen, statistics are shown here
+pub fn choose<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Result<MenuItem, Box<dyn std::error::Error>> {
+    app.screen = Screen::Menu;
+    app.home = Screen::Menu;
+    if app.menu_state.selected().is_none() {
+        app.menu_state.select(Some(0));
+    }
+    loop {
+        terminal.draw(|f| ui(f, app))?;
+        let selected = app.menu_state.selected().unwrap_or(0);
+        let item = match next_key(terminal, app)? {
+            KeyCode::Up => {
+                app.menu_state.select(Some((selected + ITEMS.len() - 1) % ITEMS.len()));
+                continue;
+            }
+            KeyCode::Down => {
+                app.menu_state.select(Some((selected + 1) % ITEMS.len()));
+                continue;
+            }
+            KeyCode::Enter => ITEMS[selected],
+            KeyCode::Esc | KeyCode::Char('q') => MenuItem::Quit,
+            _ => continue,
+        };
+        if item == MenuItem::Statistics {
+            app.screen = Screen::Stats;
+            continue;
+        }
+        app.message.clear();
+        app.screen = Screen::Game;
+        app.home = Screen::Game;
+        return Ok(item);
+    }
+}
+
+/// set `cli` and `server` for the chosen mode
+pub fn start_mode(item: MenuItem, cli: &mut Cli, server: &mut Server) {
+    match item {
+        MenuItem::Daily => {
+            cli.random = true;
+            cli.daily = true;
+            cli.practice = false;
+        }
+        MenuItem::Random | MenuItem::Practice => {
+            cli.random = true;
+            cli.daily = false;
+            cli.practice = item == MenuItem::Practice;
+            server.set_day(rand::thread_rng().gen_range(1..=server.final_count() as i32));
+        }
+        MenuItem::Custom => {
+            cli.random = false;
+            cli.daily = false;
+            cli.practice = false;
+        }
+        MenuItem::Statistics | MenuItem::Replay | MenuItem::Quit => (),
+    }
+}
+
+/// start menu screen
+pub fn draw_menu<B: Backend>(f: &mut Frame<B>, app: &mut App) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Wordle (Up/Down: select, Enter: start, Esc: quit)");
+    let area = block.inner(f.size());
+    f.render_widget(block, f.size());
+
+    let chunks = Layout::default()
+        .constraints([Constraint::Length(ITEMS.len() as u16 + 1), Constraint::Min(3)].as_ref())
+        .direction(Direction::Vertical)
+        .split(area);
+
+    let items: Vec<ListItem> = ITEMS.iter().map(|item| ListItem::new(item.label())).collect();
+    let list = List::new(items)
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("> ");
+    f.render_stateful_widget(list, chunks[0], &mut app.menu_state);
+
+    let selected = ITEMS[app.menu_state.selected().unwrap_or(0)];
+    let mut text = vec![Spans::from(selected.description()), Spans::from("")];
+    text.extend(app.message.lines().map(|line| Spans::from(line.to_string())));
+    text.push(Spans::from("Tab: statistics    F2: settings    ?/F1: help"));
+    let paragraph = Paragraph::new(text).style(app.theme.message_style()).wrap(Wrap { trim: false });
+    f.render_widget(paragraph, chunks[1]);
+}
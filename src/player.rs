@@ -2,14 +2,14 @@ use std::{collections::HashMap, cmp};
 use std::io::{Read, Write};
 use std::path::Path;
 use std::fs::File;
-use crossterm::event::{self, Event, KeyCode};
+use crossterm::event::KeyCode;
 use tui::Terminal;
 use tui::backend::Backend;
-use std::{time::Duration};
 
 use serde::{Deserialize, Serialize};
 
-use crate::{cli, App, ui};
+use crate::{cli, hint, menu, next_key, App, ui};
+use crate::timer::Speedrun;
 use cli::Cli;
 
 
@@ -20,20 +20,82 @@ struct ModeState{
     total_rounds: i32,
     #[serde(default)]
     games: Vec<Game>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    speedruns: Vec<Speedrun>,
+    /// most puzzles solved in one survival run
+    #[serde(default, skip_serializing_if = "is_zero")]
+    best_survival: usize,
+    /// practice games, not counted in the statistics
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    practice: Vec<Game>,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Game {
     pub answer: String,
     pub guesses: Vec<String>,
+    /// played in hard mode
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub difficult: bool,
+    /// milliseconds from the start of the game to each guess
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub times: Vec<u64>,
+    /// date of the daily puzzle
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    /// word states shown in lying mode, one tile of each is false
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub feedback: Vec<String>,
+    /// undo, hints and answers asked in practice mode
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub assists: Vec<String>,
+    /// tiers of hints used, see `hint::TIERS`
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub hints: usize,
+    /// answer switches of the evil host, "guess: OLD -> NEW"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub switches: Vec<String>,
+    /// answer the evil host committed to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub committed: Option<String>,
 }
 
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+fn is_zero(value: &usize) -> bool {
+    *value == 0
+}
+
+/// statistics of all games, also written to the stats json
+#[derive(Serialize, Deserialize)]
+pub struct Statistics {
+    pub win_rounds: i32,
+    pub lose_rounds: i32,
+    pub average_times: f64,
+    pub current_streak: i32,
+    pub max_streak: i32,
+    /// guess_distribution[i] is the number of games won with i + 1 guesses
+    pub guess_distribution: Vec<i32>,
+    pub hot_words: Vec<(String, i32)>,
+    /// hint_tiers[i] is the number of games with hints of tier i + 1
+    #[serde(default)]
+    pub hint_tiers: Vec<i32>,
+}
+
+/// max guess times in one game
+const MAX_TIMES: usize = 6;
+
 pub struct Player {
     pub win_rounds: i32,
     pub total_rounds: i32,
     pub win_guess_times: Vec<i32>,
     pub hot_words: HashMap<String, i32>,
     pub games: Vec<Game>,
+    pub speedruns: Vec<Speedrun>,
+    pub best_survival: usize,
+    pub practice: Vec<Game>,
 }
 
 impl Player {
@@ -44,13 +106,16 @@ impl Player {
             win_guess_times: (Vec::new()), 
             hot_words: (HashMap::new()), 
             games: (Vec::new()),
+            speedruns: (Vec::new()),
+            best_survival: (0),
+            practice: (Vec::new()),
         } 
     }
 
     pub fn read_state_before(&mut self, cli: &Cli) -> Result<(), Box<dyn std::error::Error>>{
         match &cli.state {
             Some(file_path) => {
-                if !cli.random {
+                if !cli.random && cli.command.is_none() && !menu::enabled(cli) {
                     return Err("--state/-S is only valid in random mode!".into());
                 }
                 let path = Path::new(file_path);
@@ -67,6 +132,9 @@ impl Player {
 
                 self.total_rounds = state_before.total_rounds;
                 self.games = state_before.games;
+                self.speedruns = state_before.speedruns;
+                self.best_survival = state_before.best_survival;
+                self.practice = state_before.practice;
                 for game in &self.games {
                     for guess in &game.guesses {
                         let word_count = self.hot_words.entry(guess.clone()).or_insert(0);
@@ -93,7 +161,7 @@ impl Player {
                     return Err("--state/-S only valid in random mode!".into());
                 }
                 let mut file = File::create(file_path)?;
-                let state_after: ModeState = ModeState { total_rounds: (self.total_rounds), games: (self.games.clone()) };
+                let state_after: ModeState = ModeState { total_rounds: (self.total_rounds), games: (self.games.clone()), speedruns: (self.speedruns.clone()), best_survival: (self.best_survival), practice: (self.practice.clone()) };
                 let contents = serde_json::to_string(&state_after)?;
                 file.write(contents.as_bytes())?;
                 return Ok(());
@@ -114,6 +182,55 @@ impl Player {
         (average_times * 100.0).round() / 100.0
     }
 
+    /// win streaks computed from `games`, return (current streak, max streak)
+    pub fn win_streaks(&self) -> (i32, i32) {
+        let mut current_streak = 0;
+        let mut max_streak = 0;
+        for game in &self.games {
+            if game.guesses.last() == Some(&game.answer) {
+                current_streak += 1;
+                max_streak = cmp::max(max_streak, current_streak);
+            } else {
+                current_streak = 0;
+            }
+        }
+        (current_streak, max_streak)
+    }
+
+    /// number of won games for each guess times 1..=6
+    pub fn guess_distribution(&self) -> Vec<i32> {
+        let mut distribution = vec![0; MAX_TIMES];
+        for game in &self.games {
+            if game.guesses.last() == Some(&game.answer) && game.guesses.len() <= MAX_TIMES {
+                distribution[game.guesses.len() - 1] += 1;
+            }
+        }
+        distribution
+    }
+
+    pub fn statistics(&self) -> Statistics {
+        let (current_streak, max_streak) = self.win_streaks();
+        Statistics {
+            win_rounds: self.win_rounds,
+            lose_rounds: self.total_rounds - self.win_rounds,
+            average_times: self.average_times(),
+            current_streak,
+            max_streak,
+            guess_distribution: self.guess_distribution(),
+            hot_words: self.get_sorted_hot_words(),
+            hint_tiers: hint::tier_counts(&self.games),
+        }
+    }
+
+    /// write statistics into the stats json
+    pub fn write_stats_after(&self, cli: &Cli) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(file_path) = &cli.stats_json {
+            let contents = serde_json::to_string_pretty(&self.statistics())?;
+            std::fs::write(file_path, contents)?;
+        }
+        Ok(())
+    }
+
     pub fn get_sorted_hot_words(&self) -> Vec<(String, i32)> {
         let mut hot_words_vec: Vec<(String, i32)> = Vec::new();
         for (key,value) in &self.hot_words {
@@ -146,6 +263,13 @@ impl Player {
             app.message += ", average times: ";
             app.message += z.to_string().as_str();
 
+            let (current_streak, max_streak) = self.win_streaks();
+            app.message += "\ncurrent streak: ";
+            app.message += current_streak.to_string().as_str();
+            app.message += ", max streak: ";
+            app.message += max_streak.to_string().as_str();
+            app.guess_distribution = self.guess_distribution();
+
             let sorted_hot_words = self.get_sorted_hot_words(); 
             // hot words:
             app.message += "\nHot words: ";
@@ -160,26 +284,21 @@ impl Player {
             }
             
         }
-        app.message += "\nNext game: Y/N ...30 seconds before next round";
+        app.message += "\nNext game: Y/N (Tab: statistics)";
         terminal.draw(|f| ui(f, app))?;
         // write json
 
         // next game ?
-        if crossterm::event::poll(Duration::from_secs(30))?{
-            if let Event::Key(key) = event::read()? {
-                match key.code {
-                    KeyCode::Char(ch) => {
-                        match ch {
-                            'y' => return Ok(true),
-                            'Y' => return Ok(true),
-                            _ => return Ok(false),
-                        }
-                    }
-                    _ => return Ok(false),
+        match next_key(terminal, app)? {
+            KeyCode::Char(ch) => {
+                match ch {
+                    'y' => Ok(true),
+                    'Y' => Ok(true),
+                    _ => Ok(false),
                 }
             }
+            _ => Ok(false),
         }
-        return  Ok(true);
 
     }
 
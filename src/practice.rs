@@ -0,0 +1,37 @@
+use crossterm::event::KeyCode;
+
+use crate::server::Server;
+
+/// keys of practice mode, shown in the message
+pub const CONTROLS: &str = "F3: undo  F4: letter  F5: candidates  F6: solver  F7: answer";
+
+/// assistance asked by `key` in practice mode, (message, record in the game), None if the key asks for nothing
+pub fn assist(key: KeyCode, server: &mut Server, word_states: &Vec<Vec<u8>>, guess_words: &Vec<String>) -> Option<(String, String)> {
+    match key {
+        KeyCode::F(4) => {
+            // the first letter not found as G yet
+            let position = (0..5).find(|x| !word_states.iter().any(|word_state| word_state[*x] == b'G'));
+            match position {
+                Some(position) => {
+                    let letter = server.answer.as_bytes()[position] as char;
+                    Some((format!("Letter {} is {}", position + 1, letter), format!("letter {} {}", position + 1, letter)))
+                }
+                None => Some(("All letters are found".to_string(), "letter -".to_string())),
+            }
+        }
+        KeyCode::F(5) => {
+            server.get_all_possible_answers(word_states, guess_words);
+            let count = server.possible_answer.len();
+            Some((format!("{} possible answers", count), format!("candidates {}", count)))
+        }
+        KeyCode::F(6) => {
+            server.get_all_possible_answers(word_states, guess_words);
+            match server.best_guess() {
+                Some((word, _entropy)) => Some((format!("Solver would guess {}", word), format!("solver {}", word))),
+                None => Some(("Solver: too many possible answers".to_string(), "solver -".to_string())),
+            }
+        }
+        KeyCode::F(7) => Some((format!("The answer is {}", server.answer), "answer".to_string())),
+        _ => None,
+    }
+}
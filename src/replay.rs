@@ -0,0 +1,78 @@
+use crossterm::event::KeyCode;
+use tui::{backend::Backend, Terminal};
+
+use crate::{compare_secret_guess, next_key, ui, update_alphabet_state, App};
+use crate::analysis::{analyze_game, GuessAnalysis};
+use crate::player::Player;
+use crate::server::Server;
+
+/// show the first `step` guesses of a game
+fn show_step(game_index: usize, game_count: usize, answer: &str, feedback: &[String], steps: &[GuessAnalysis], step: usize, app: &mut App) {
+    app.guess_words.clear();
+    app.word_states.clear();
+    app.alphabet_state = vec![b'X'; 26];
+    for (index, replay_step) in steps[..step].iter().enumerate() {
+        let mut word_state = Vec::new();
+        match feedback.get(index) {
+            Some(shown) => {
+                word_state = shown.as_bytes().to_vec();
+                update_alphabet_state(&replay_step.guess, &word_state, &mut app.alphabet_state);
+            }
+            None => {
+                compare_secret_guess(&answer.to_string(), &replay_step.guess, &mut word_state, &mut app.alphabet_state);
+            }
+        }
+        app.guess_words.push(replay_step.guess.clone());
+        app.word_states.push(word_state);
+    }
+
+    app.message = format!("Replay game {}/{}: {}, guess {}/{}", game_index + 1, game_count, answer, step, steps.len());
+    if step > 0 {
+        let replay_step = &steps[step - 1];
+        app.message += &format!(
+            "\nCandidates: {} -> {}, {}: expected {:.2} bits, got {:.2} bits",
+            replay_step.candidates_before, replay_step.candidates_after, replay_step.guess, replay_step.expected_bits, replay_step.actual_bits
+        );
+        match &replay_step.best {
+            Some((word, bits)) => app.message += &format!("\nSolver would guess: {} {:.2} bits", word, bits),
+            None => app.message += "\nSolver would guess: - (too many candidates)",
+        }
+    } else {
+        app.message += "\n\n";
+    }
+    app.message += "\nLeft/Right: step, Up/Down: game, Esc: quit";
+}
+
+/// replay games in the state json guess by guess
+pub fn replay_games<B: Backend>(server: &mut Server, player: &Player, game: Option<usize>, terminal: &mut Terminal<B>, app: &mut App) -> Result<(), Box<dyn std::error::Error>> {
+    if player.games.is_empty() {
+        return Err("no game recorded in state json!".into());
+    }
+    let mut game_index = match game {
+        Some(n) if n >= 1 && n <= player.games.len() => n - 1,
+        Some(_n) => return Err("no such game in state json!".into()),
+        None => player.games.len() - 1,
+    };
+
+    let mut steps = analyze_game(server, &player.games[game_index]).guesses;
+    let mut step = steps.len();
+    loop {
+        let game = &player.games[game_index];
+        show_step(game_index, player.games.len(), &game.answer.to_uppercase(), &game.feedback, &steps, step, app);
+        terminal.draw(|f| ui(f, app))?;
+
+        let last_index = game_index;
+        match next_key(terminal, app)? {
+            KeyCode::Esc => return Ok(()),
+            KeyCode::Left => step = step.saturating_sub(1),
+            KeyCode::Right => step = (step + 1).min(steps.len()),
+            KeyCode::Up => game_index = game_index.saturating_sub(1),
+            KeyCode::Down => game_index = (game_index + 1).min(player.games.len() - 1),
+            _ => (),
+        }
+        if game_index != last_index {
+            steps = analyze_game(server, &player.games[game_index]).guesses;
+            step = 0;
+        }
+    }
+}
@@ -1,108 +1,55 @@
 use rand::{rngs::StdRng, SeedableRng};
 use rand::prelude::*;
 use tui::backend::Backend;
-use std::cmp::min;
+use std::cmp::{min, Reverse};
 use std::fs::File;
 use std::io::Read;
-use std::{collections::HashSet};
+use std::collections::{HashMap, HashSet};
 use ordered_float::OrderedFloat;
 use rayon::prelude::*;
-use crossterm::event::{self, Event, KeyCode};
-use std::{io, time::Duration};
-use crossterm::{
-    event::{ DisableMouseCapture},
-    execute,
-    terminal::{disable_raw_mode, LeaveAlternateScreen},
-};
-use tui::{
-    backend::{ CrosstermBackend},
-    Terminal, 
-};
-
-use crate::{builtin_words, get_word_state, App, ui};
+use crossterm::event::KeyCode;
+use tui::Terminal;
+
+use crate::{builtin_words, challenge, exit, fibble, get_word_state, next_key, App, ui};
 use builtin_words::FINAL;
 use builtin_words::ACCEPTABLE;
 
 use crate::cli;
 use cli::Cli;
 
+/// max possible answers to search the best guess, the search is O(n^2)
+pub const SOLVER_LIMIT: usize = 1000;
+
 pub struct Server{
     pub answer: String,
+    /// day of current game
+    pub day: i32,
     final_words: Vec<String>,
     acceptable_words: Vec<String>,
     rounds: i32,
     pub possible_answer: Vec<String>,
     entropy_count: i32,
+    /// lying mode, one tile of each word state is false
+    pub fibble: bool,
 }
 
 impl Server {
     pub fn new(cli: &Cli) -> Server {
         Server { 
             answer: (String::new()), 
+            day: (cli.day.unwrap()),
             final_words: (Vec::new()),
             acceptable_words: (Vec::new()),
             rounds: (cli.day.unwrap()),
             possible_answer: (Vec::new()),
             entropy_count: (0),
+            fibble: (cli.fibble),
         }
     }
 
     // procee final word list and acceptable word list
     pub fn word_list_process(&mut self, cli: &Cli) -> Result<(), Box<dyn std::error::Error>> {
-        // check final-set
-        match &cli.final_set {
-            Some(file_path) => {
-                let mut file = File::open(file_path)?;
-                let mut final_contents = String::new();
-                file.read_to_string(&mut final_contents)?;
-                final_contents = final_contents.trim().to_string();
-                self.final_words = final_contents.split("\n").map(|s| s.trim().to_uppercase()).collect();
-                let final_word_set: HashSet<_> = self.final_words.iter().collect();
-                
-                if final_word_set.len() < self.final_words.len() {
-                    return Err("final word list have same word!".into());
-                }
-
-                for word in &final_word_set {
-                    if !word_basic_check(&word.to_string()){
-                        return Err("error final word!".into());
-                    }
-                }
-                
-            }
-            None => {
-                self.final_words = FINAL.to_vec().iter().map(|s| s.trim().to_uppercase()).collect()
-            }
-        }
-        // check acceptable-set
-        match &cli.acceptable_set {
-            Some(file_path) => {
-                let mut file = File::open(file_path)?;
-                let mut acceptable_contents = String::new();
-                file.read_to_string(&mut acceptable_contents)?;
-                acceptable_contents = acceptable_contents.trim().to_string();
-                self.acceptable_words = acceptable_contents.split("\n").map(|s| s.trim().to_uppercase()).collect();
-                let acceptable_word_set: HashSet<_> = self.final_words.iter().collect();
-                
-                if acceptable_word_set.len() < self.final_words.len() {
-                    return Err("acceptable word list have same word!".into());
-                }
-
-                for word in &acceptable_word_set {
-                    if !word_basic_check(&word.to_string()){
-                        return Err("error acceptable word!".into());
-                    }
-                }
-            }
-            None => self.acceptable_words = ACCEPTABLE.to_vec().iter().map(|s| s.trim().to_uppercase()).collect(),
-        }
-
-        // subset
-        let final_word_set: HashSet<_> = self.final_words.iter().collect();
-        let acceptable_word_set: HashSet<_> = self.acceptable_words.iter().collect();
-        if !final_word_set.is_subset(&acceptable_word_set) {
-            return Err("final is not subset of acceptable!".into());
-        }
+        (self.final_words, self.acceptable_words) = load_word_lists(&cli.final_set, &cli.acceptable_set)?;
 
         // sort by dirctionary list
         self.acceptable_words.sort_by(|a, b| a.cmp(b));    
@@ -115,10 +62,37 @@ impl Server {
         Ok(())
     }
 
+    /// next random mode game plays the puzzle of `day`
+    pub fn set_day(&mut self, day: i32) {
+        self.rounds = day;
+    }
+
+    /// day of the next random mode game
+    pub fn next_day(&self) -> i32 {
+        self.rounds
+    }
+
+    pub fn final_count(&self) -> usize {
+        self.final_words.len()
+    }
+
+    pub fn is_final(&self, word: &str) -> bool {
+        self.final_words.iter().any(|x| x == word)
+    }
+
+    /// identity of the final word list, the same for the same words in any order
+    pub fn final_list_id(&self) -> u16 {
+        let mut words = self.final_words.clone();
+        words.sort();
+        (challenge::fnv1a(words.join("\n").as_bytes()) & 0xffff) as u16
+    }
+
     /// initialize secret word
     pub fn init_secret_word<B: Backend>(&mut self, cli: &Cli, terminal: &mut Terminal<B>, app: &mut App) -> Result<(), Box<dyn std::error::Error>>{
         if cli.random {
-            self.answer = self.final_words[self.rounds as usize - 1].clone();
+            // endless runs start over from the first word
+            self.answer = self.final_words[(self.rounds as usize - 1) % self.final_words.len()].clone();
+            self.day = self.rounds;
             self.rounds += 1;
         }
         else {
@@ -129,58 +103,44 @@ impl Server {
                     app.message = "Welcome to Wordle!\nPlease input word for guess:".to_string();
                     app.guess_words.clear();
                     app.word_states.clear();
-                    app.guess_words.push(String::new());
-                    app.word_states.push(Vec::new());
-                    terminal.draw(|f| ui(f, app))?;
-                    app.word_states.pop();
-                    app.guess_words.pop();
         
                     // Please input a word which has 5 bytes:
                     // process keyboard input
                     // block
+                    // the word is masked, the other player is watching
                     let mut word = String::new();
                     let mut word_state: Vec<u8> = Vec::new();
-                    while crossterm::event::poll(Duration::from_secs(60))?{
-                        if let Event::Key(key) = event::read()? {
-                            match key.code {
-                                KeyCode::Esc => {
-                                    // come back terminal
-                                    let backend = CrosstermBackend::new(io::stdout());
-                                    let mut terminal = Terminal::new(backend)?;
-                                    disable_raw_mode()?;
-                                    execute!(
-                                        terminal.backend_mut(),
-                                        LeaveAlternateScreen,
-                                        DisableMouseCapture
-                                    )?;
-                                    terminal.show_cursor()?;
-                                    return Err("Force Quit.".into());
-                                }
-                                KeyCode::Char(ch) => {
-                                    if word.len() < 5 {
-                                        word.push(ch);
-                                        word_state.push(88);
-                                        app.guess_words.push(word.clone());
-                                        app.word_states.push(word_state.clone());
-                                        terminal.draw(|f| ui(f, app))?;
-                                        app.guess_words.pop();
-                                        app.word_states.pop();
+                    loop {
+                        app.guess_words.push("*".repeat(word.len()));
+                        app.word_states.push(word_state.clone());
+                        terminal.draw(|f| ui(f, app))?;
+                        let key = next_key(terminal, app)?;
+                        app.guess_words.pop();
+                        app.word_states.pop();
+
+                        match key {
+                            KeyCode::Enter => {
+                                let mut answer = word.trim().to_string();
+                                answer.make_ascii_uppercase();
+                                match self.secret_word_problem(&answer) {
+                                    Some(problem) => {
+                                        app.message = format!("{}\nPlease input word for guess:", problem);
+                                        word.clear();
+                                        word_state.clear();
                                     }
+                                    None => break,
                                 }
-                                KeyCode::Enter => {
-                                    break;
-                                }
-                                KeyCode::Backspace => {
-                                    word.pop();
-                                    word_state.pop();
-                                    app.guess_words.push(word.clone());
-                                    app.word_states.push(word_state.clone());
-                                    terminal.draw(|f| ui(f, app))?;
-                                    app.guess_words.pop();
-                                    app.word_states.pop();
-                                }
-                                _ => {}
                             }
+                            KeyCode::Esc => exit::confirm_quit(terminal, app)?,
+                            KeyCode::Char(ch) if word.len() < 5 => {
+                                word.push(ch);
+                                word_state.push(88);
+                            }
+                            KeyCode::Backspace => {
+                                word.pop();
+                                word_state.pop();
+                            }
+                            _ => {}
                         }
                     }
                     self.answer = word.trim().to_string();
@@ -192,6 +152,44 @@ impl Server {
         Ok(())
     }
 
+    /// evil host, switch the answer to the largest group of final words giving the same word state to `guess`,
+    /// only words consistent with the word states so far are used, return (old answer, new answer) if switched
+    pub fn evil_switch(&mut self, guess: &String, word_states: &[Vec<u8>], guess_words: &[String]) -> Option<(String, String)> {
+        let answer = self.answer.to_uppercase();
+        let mut groups: HashMap<Vec<u8>, Vec<&String>> = HashMap::new();
+        for word in &self.final_words {
+            let consistent = guess_words
+                .iter()
+                .zip(word_states)
+                .all(|(guess_word, word_state)| &get_word_state(word, guess_word) == word_state);
+            if consistent {
+                groups.entry(get_word_state(word, guess)).or_default().push(word);
+            }
+        }
+
+        // larger group first, then a group not solved by the guess, then the group of the answer
+        let (_word_state, words) = groups.into_iter().max_by_key(|(word_state, words)| {
+            (words.len(), word_state.iter().any(|x| *x != b'G'), words.contains(&&answer), Reverse(words[0].clone()))
+        })?;
+        if words.contains(&&answer) {
+            return None;
+        }
+        let new_answer = words[0].clone();
+        self.answer = new_answer.clone();
+        Some((answer, new_answer))
+    }
+
+    /// why the typed secret word can't be the answer, None if it can
+    fn secret_word_problem(&self, word: &String) -> Option<String> {
+        if !word_basic_check(word) {
+            return Some("The word should be 5 letters from A to Z!".to_string());
+        }
+        if !self.is_final(word) {
+            return Some("That word is not in the final word list!".to_string());
+        }
+        None
+    }
+
     
     /// word is valid or not
     pub fn is_valid(&self, word: &String, last_word_state: &Vec<u8>, last_guess_word: &String, is_hard: bool) -> bool {
@@ -217,7 +215,7 @@ impl Server {
         false
     }
 
-    fn get_all_possible_answers(&mut self, word_states: &Vec<Vec<u8>>, guess_words: &Vec<String>) {
+    pub fn get_all_possible_answers(&mut self, word_states: &Vec<Vec<u8>>, guess_words: &Vec<String>) {
         self.possible_answer.clear();
 
         if guess_words.len() == 0 {
@@ -228,7 +226,12 @@ impl Server {
                 let mut all_meet: usize = 0;
 
                 for i in 0..guess_words.len() {
-                    if word_perfect_check(acceptable_word, &word_states[i], &guess_words[i]) {
+                    let meet = if self.fibble {
+                        fibble::word_lying_check(acceptable_word, &word_states[i], &guess_words[i])
+                    } else {
+                        word_perfect_check(acceptable_word, &word_states[i], &guess_words[i])
+                    };
+                    if meet {
                         all_meet += 1;
                     }
                 }
@@ -247,19 +250,9 @@ impl Server {
             Some( n ) => {
                 self.get_all_possible_answers(word_states, guess_words);
                 
-                let mut recommend_words_vec: Vec<(String, f64)> = Vec::new();
-                
                 self.entropy_count = 0;
 
-                // rayon for speed!
-                self.possible_answer
-                    .par_iter()
-                    .map(|x| (x.to_string(), self.compute_entropy_for_one(x)))
-                    .collect_into_vec(&mut recommend_words_vec);
-
-
-                recommend_words_vec.sort_by(|a, b| a.0.cmp(&b.0));  
-                recommend_words_vec.sort_by(|a, b| OrderedFloat(b.1).cmp(&OrderedFloat(a.1)));
+                let recommend_words_vec = self.get_sorted_entropy();
 
                 app.message += "\nPossible answer and entropy:\n";
                 let len = min(self.possible_answer.len(), *n as usize);
@@ -280,10 +273,33 @@ impl Server {
     }
     
 
-    fn compute_entropy_for_one(&self, word: &String) -> f64 {
+    /// entropy of all possible answers, sorted from max entropy
+    pub fn get_sorted_entropy(&self) -> Vec<(String, f64)> {
+        let mut recommend_words_vec: Vec<(String, f64)> = Vec::new();
+
+        // rayon for speed!
+        self.possible_answer
+            .par_iter()
+            .map(|x| (x.to_string(), self.compute_entropy_for_one(x)))
+            .collect_into_vec(&mut recommend_words_vec);
+
+        recommend_words_vec.sort_by(|a, b| a.0.cmp(&b.0));  
+        recommend_words_vec.sort_by(|a, b| OrderedFloat(b.1).cmp(&OrderedFloat(a.1)));
+        recommend_words_vec
+    }
+
+    /// the word with max entropy in possible answers, None if there are too many possible answers
+    pub fn best_guess(&self) -> Option<(String, f64)> {
+        if self.possible_answer.len() > SOLVER_LIMIT {
+            return None;
+        }
+        self.get_sorted_entropy().into_iter().next()
+    }
+
+    /// number of possible answers for each of the 243 word states after guessing `word`
+    pub fn pattern_counts(&self, word: &String) -> Vec<i32> {
 
         let mut all_match_count: Vec<i32> = vec![0; 243];
-        let total_count = self.possible_answer.len() as f64; 
         let mut match_word_states: Vec<Vec<u8>> = Vec::new();
 
         self.possible_answer
@@ -292,19 +308,18 @@ impl Server {
             .collect_into_vec(&mut match_word_states);
 
         for word_state in &match_word_states {
-            
-            // word state => index
-            let mut index: i32 = 0;
-            for letter in word_state {
-                match letter {
-                    82 => index = index * 3,
-                    89 => index = index * 3 + 1,
-                    71 => index = index * 3 + 2,
-                    _ => (),
-                }
-            }
-            all_match_count[index as usize] += 1;
+            all_match_count[word_state_index(word_state)] += 1;
+        }
+        if self.fibble {
+            return fibble::lying_pattern_counts(&all_match_count);
         }
+        all_match_count
+    }
+
+    pub fn compute_entropy_for_one(&self, word: &String) -> f64 {
+
+        let mut all_match_count: Vec<i32> = self.pattern_counts(word);
+        let total_count = all_match_count.iter().sum::<i32>() as f64;
         
         // filter 0
         all_match_count = all_match_count.into_par_iter().filter(|x| *x != 0).collect();
@@ -320,7 +335,83 @@ impl Server {
 }
 
 
-fn word_basic_check(word: &String) -> bool {
+/// read final word list and acceptable word list, builtin lists if the file is not given
+pub fn load_word_lists(final_set: &Option<String>, acceptable_set: &Option<String>) -> Result<(Vec<String>, Vec<String>), Box<dyn std::error::Error>> {
+    let final_words: Vec<String>;
+    let acceptable_words: Vec<String>;
+    // check final-set
+    match final_set {
+        Some(file_path) => {
+            let mut file = File::open(file_path)?;
+            let mut final_contents = String::new();
+            file.read_to_string(&mut final_contents)?;
+            final_contents = final_contents.trim().to_string();
+            final_words = final_contents.split("\n").map(|s| s.trim().to_uppercase()).collect();
+            let final_word_set: HashSet<_> = final_words.iter().collect();
+            
+            if final_word_set.len() < final_words.len() {
+                return Err("final word list have same word!".into());
+            }
+
+            for word in &final_word_set {
+                if !word_basic_check(&word.to_string()){
+                    return Err("error final word!".into());
+                }
+            }
+            
+        }
+        None => {
+            final_words = FINAL.to_vec().iter().map(|s| s.trim().to_uppercase()).collect()
+        }
+    }
+    // check acceptable-set
+    match acceptable_set {
+        Some(file_path) => {
+            let mut file = File::open(file_path)?;
+            let mut acceptable_contents = String::new();
+            file.read_to_string(&mut acceptable_contents)?;
+            acceptable_contents = acceptable_contents.trim().to_string();
+            acceptable_words = acceptable_contents.split("\n").map(|s| s.trim().to_uppercase()).collect();
+            let acceptable_word_set: HashSet<_> = final_words.iter().collect();
+            
+            if acceptable_word_set.len() < final_words.len() {
+                return Err("acceptable word list have same word!".into());
+            }
+
+            for word in &acceptable_word_set {
+                if !word_basic_check(&word.to_string()){
+                    return Err("error acceptable word!".into());
+                }
+            }
+        }
+        None => acceptable_words = ACCEPTABLE.to_vec().iter().map(|s| s.trim().to_uppercase()).collect(),
+    }
+
+    // subset
+    let final_word_set: HashSet<_> = final_words.iter().collect();
+    let acceptable_word_set: HashSet<_> = acceptable_words.iter().collect();
+    if !final_word_set.is_subset(&acceptable_word_set) {
+        return Err("final is not subset of acceptable!".into());
+    }
+
+    Ok((final_words, acceptable_words))
+}
+
+/// word state => index in 0..243
+pub fn word_state_index(word_state: &[u8]) -> usize {
+    let mut index: usize = 0;
+    for letter in word_state {
+        match letter {
+            82 => index *= 3,
+            89 => index = index * 3 + 1,
+            71 => index = index * 3 + 2,
+            _ => (),
+        }
+    }
+    index
+}
+
+pub fn word_basic_check(word: &String) -> bool {
     if word.len() != 5 {
         return false;
     }
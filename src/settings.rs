@@ -0,0 +1,281 @@
+use std::fs;
+use crossterm::event::KeyCode;
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::{help, App};
+use crate::cli::Cli;
+use crate::server::{load_word_lists, Server};
+use crate::theme::Theme;
+
+const BUILTIN: &str = "builtin";
+const THEMES: [&str; 3] = ["default", "high-contrast", "monochrome"];
+/// directory to look for word list files
+const WORD_LIST_DIR: &str = "data";
+
+/// settings edited in the terminal ui, applied to `Cli` between games
+pub struct Settings {
+    pub difficult: bool,
+    pub prompt: Option<i32>,
+    pub final_set: Option<String>,
+    pub acceptable_set: Option<String>,
+    pub theme: Option<String>,
+    /// settings are changed but not applied
+    pub changed: bool,
+    /// the chosen word lists are broken and won't be applied
+    word_list_error: Option<String>,
+    /// the config json can't be written
+    save_error: Option<String>,
+    pub list_state: ListState,
+    /// word list files to choose from
+    word_list_files: Vec<String>,
+    /// themes to choose from
+    themes: Vec<String>,
+}
+
+impl Settings {
+    pub fn new() -> Settings {
+        Settings {
+            difficult: false,
+            prompt: None,
+            final_set: None,
+            acceptable_set: None,
+            theme: None,
+            changed: false,
+            word_list_error: None,
+            save_error: None,
+            list_state: ListState::default(),
+            word_list_files: Vec::new(),
+            themes: Vec::new(),
+        }
+    }
+
+    pub fn from_cli(cli: &Cli) -> Settings {
+        let mut word_list_files: Vec<String> = vec![BUILTIN.to_string()];
+        if let Ok(entries) = fs::read_dir(WORD_LIST_DIR) {
+            let mut files: Vec<String> = entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path().to_string_lossy().to_string())
+                .filter(|path| path.ends_with(".txt"))
+                .collect();
+            files.sort();
+            word_list_files.append(&mut files);
+        }
+        for file in [&cli.final_set, &cli.acceptable_set].into_iter().flatten() {
+            if !word_list_files.contains(file) {
+                word_list_files.push(file.clone());
+            }
+        }
+
+        let mut themes: Vec<String> = THEMES.iter().map(|x| x.to_string()).collect();
+        if let Some(theme) = &cli.theme {
+            if !themes.contains(theme) {
+                themes.push(theme.clone());
+            }
+        }
+
+        let mut list_state = ListState::default();
+        list_state.select(Some(0));
+        Settings {
+            difficult: cli.difficult,
+            prompt: cli.prompt,
+            final_set: cli.final_set.clone(),
+            acceptable_set: cli.acceptable_set.clone(),
+            theme: cli.theme.clone(),
+            changed: false,
+            word_list_error: None,
+            save_error: None,
+            list_state,
+            word_list_files,
+            themes,
+        }
+    }
+
+    fn items(&self) -> Vec<(&'static str, String)> {
+        let on_off = if self.difficult { "on" } else { "off" };
+        vec![
+            ("Hard mode", on_off.to_string()),
+            ("Prompt words", self.prompt.map(|x| x.to_string()).unwrap_or("off".to_string())),
+            ("Final words", self.final_set.clone().unwrap_or(BUILTIN.to_string())),
+            ("Acceptable words", self.acceptable_set.clone().unwrap_or(BUILTIN.to_string())),
+            ("Theme", self.theme.clone().unwrap_or(THEMES[0].to_string())),
+        ]
+    }
+
+    /// save the settings to the config json as soon as they are changed, other keys are kept,
+    /// broken word lists are not saved
+    fn save(&self, config_path: &Option<String>) -> Result<(), Box<dyn std::error::Error>> {
+        let file_path = match config_path {
+            Some(file_path) => file_path,
+            None => return Ok(()),
+        };
+        let mut config = match fs::read_to_string(file_path) {
+            Ok(contents) => serde_json::from_str::<serde_json::Value>(&contents)?,
+            Err(_) => serde_json::json!({}),
+        };
+        let object = match config.as_object_mut() {
+            Some(object) => object,
+            None => return Err("config json is not an object!".into()),
+        };
+        object.insert("difficult".to_string(), serde_json::json!(self.difficult));
+        let mut values = vec![
+            ("prompt", self.prompt.map(|x| serde_json::json!(x))),
+            ("theme", self.theme.as_ref().map(|x| serde_json::json!(x))),
+        ];
+        if self.word_list_error.is_none() {
+            values.push(("final_set", self.final_set.as_ref().map(|x| serde_json::json!(x))));
+            values.push(("acceptable_set", self.acceptable_set.as_ref().map(|x| serde_json::json!(x))));
+        }
+        for (key, value) in values {
+            match value {
+                Some(value) => object.insert(key.to_string(), value),
+                None => object.remove(key),
+            };
+        }
+        fs::write(file_path, serde_json::to_string_pretty(&config)?)?;
+        Ok(())
+    }
+}
+
+/// next or previous choice in `choices`, `None` means the first choice
+fn cycle(choices: &[String], current: &Option<String>, forward: bool) -> Option<String> {
+    let index = match current {
+        Some(current) => choices.iter().position(|x| x == current).unwrap_or(0),
+        None => 0,
+    };
+    let index = if forward { (index + 1) % choices.len() } else { (index + choices.len() - 1) % choices.len() };
+    if index == 0 {
+        None
+    } else {
+        Some(choices[index].clone())
+    }
+}
+
+/// keys on settings screen
+pub fn handle_key(app: &mut App, code: KeyCode) {
+    let settings = &mut app.settings;
+    let len = settings.items().len();
+    let selected = settings.list_state.selected().unwrap_or(0);
+    let forward = match code {
+        KeyCode::Esc | KeyCode::F(2) => {
+            app.screen = app.home;
+            return;
+        }
+        KeyCode::Up => {
+            settings.list_state.select(Some((selected + len - 1) % len));
+            return;
+        }
+        KeyCode::Down => {
+            settings.list_state.select(Some((selected + 1) % len));
+            return;
+        }
+        KeyCode::Left => false,
+        KeyCode::Right | KeyCode::Enter | KeyCode::Char(' ') => true,
+        _ => return,
+    };
+
+    match selected {
+        0 => settings.difficult = !settings.difficult,
+        1 => {
+            let prompt = settings.prompt.unwrap_or(0) + if forward { 1 } else { -1 };
+            settings.prompt = if prompt <= 0 { None } else { Some(prompt) };
+        }
+        2 | 3 => {
+            if selected == 2 {
+                settings.final_set = cycle(&settings.word_list_files, &settings.final_set, forward);
+            } else {
+                settings.acceptable_set = cycle(&settings.word_list_files, &settings.acceptable_set, forward);
+            }
+            settings.word_list_error = load_word_lists(&settings.final_set, &settings.acceptable_set)
+                .err()
+                .map(|error| error.to_string());
+        }
+        _ => {
+            let theme = cycle(&settings.themes, &settings.theme, forward);
+            // keep the old theme if the theme json is broken
+            if let Ok(new_theme) = Theme::load(&theme) {
+                app.theme = new_theme;
+                settings.theme = theme;
+            }
+        }
+    }
+    settings.changed = true;
+    settings.save_error = settings.save(&app.config_path).err().map(|error| error.to_string());
+}
+
+/// settings screen
+pub fn draw_settings<B: Backend>(f: &mut Frame<B>, app: &mut App) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Settings (Up/Down: select, Left/Right/Enter: change, Esc: back)");
+    let area = block.inner(f.size());
+    f.render_widget(block, f.size());
+
+    let chunks = Layout::default()
+        .constraints([Constraint::Length(7), Constraint::Min(3)].as_ref())
+        .direction(Direction::Vertical)
+        .split(area);
+
+    let items: Vec<ListItem> = app.settings
+        .items()
+        .into_iter()
+        .map(|(name, value)| ListItem::new(format!("{:<18}< {} >", name, value)))
+        .collect();
+    let list = List::new(items)
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("> ");
+    f.render_stateful_widget(list, chunks[0], &mut app.settings.list_state);
+
+    let save = match &app.config_path {
+        Some(file_path) => format!("Changes are saved to {}.", file_path),
+        None => "Changes are not saved, use -c/--config to save them.".to_string(),
+    };
+    let mut text = vec![
+        Spans::from("Changes apply from the next game, the theme applies now."),
+        Spans::from(save),
+    ];
+    if let Some(error) = &app.settings.word_list_error {
+        text.push(Spans::from(""));
+        text.push(Spans::from(Span::styled(
+            format!("Word lists won't be changed: {}", error),
+            Style::default().fg(app.theme.absent).add_modifier(Modifier::BOLD),
+        )));
+    }
+    if let Some(error) = &app.settings.save_error {
+        text.push(Spans::from(""));
+        text.push(Spans::from(Span::styled(
+            format!("Changes can't be saved: {}", error),
+            Style::default().fg(app.theme.absent).add_modifier(Modifier::BOLD),
+        )));
+    }
+    f.render_widget(Paragraph::new(text).wrap(Wrap { trim: false }), chunks[1]);
+}
+
+/// apply changed settings to `cli` and `server` between games, they are already saved to the config json
+pub fn apply(cli: &mut Cli, server: &mut Server, app: &mut App) -> Result<(), Box<dyn std::error::Error>> {
+    if !app.settings.changed {
+        return Ok(());
+    }
+    app.settings.changed = false;
+
+    let settings = &app.settings;
+    cli.difficult = settings.difficult;
+    cli.prompt = settings.prompt;
+    cli.theme = settings.theme.clone();
+    if settings.word_list_error.is_none()
+        && (cli.final_set != settings.final_set || cli.acceptable_set != settings.acceptable_set)
+    {
+        cli.final_set = settings.final_set.clone();
+        cli.acceptable_set = settings.acceptable_set.clone();
+        server.word_list_process(cli)?;
+    }
+
+    app.session_info = help::session_info(cli);
+    Ok(())
+}
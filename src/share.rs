@@ -0,0 +1,70 @@
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+
+use crate::App;
+use crate::cli::Cli;
+
+/// result grid of a finished game, like "Wordle 5 4/6*" and rows of 🟩🟨⬛
+pub fn result_grid(day: i32, word_states: &[Vec<u8>], win: bool, difficult: bool) -> String {
+    let times = if win { word_states.len().to_string() } else { "X".to_string() };
+    let hard = if difficult { "*" } else { "" };
+    let mut grid = format!("Wordle {} {}/6{}\n", day, times, hard);
+    for word_state in word_states {
+        grid.push('\n');
+        for letter in word_state {
+            match letter {
+                71 => grid.push('🟩'),
+                89 => grid.push('🟨'),
+                _ => grid.push('⬛'),
+            }
+        }
+    }
+    grid
+}
+
+/// share the result grid to stdout, the system clipboard or a file
+pub fn share_result(cli: &Cli, day: i32, word_states: &[Vec<u8>], win: bool, app: &mut App) -> Result<(), Box<dyn std::error::Error>> {
+    let target = match &cli.share {
+        Some(target) => target,
+        None => return Ok(()),
+    };
+    let grid = result_grid(day, word_states, win, cli.difficult);
+    match target.as_str() {
+        // stdout is used by the terminal ui, print after quit
+        "stdout" => {
+            app.share_output += &grid;
+            app.share_output += "\n\n";
+            app.message += "\nResult will be printed after quit";
+        }
+        "clipboard" => {
+            // OSC 52: ask the terminal to set the clipboard
+            let mut stdout = io::stdout();
+            write!(stdout, "\x1b]52;c;{}\x07", base64_encode(grid.as_bytes()))?;
+            stdout.flush()?;
+            app.message += "\nResult copied to clipboard";
+        }
+        file_path => {
+            let mut file = OpenOptions::new().create(true).append(true).open(file_path)?;
+            writeln!(file, "{}\n", grid)?;
+            app.message += "\nResult saved to ";
+            app.message += file_path;
+        }
+    }
+    Ok(())
+}
+
+fn base64_encode(bytes: &[u8]) -> String {
+    const TABLE: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::new();
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |acc, (i, x)| acc | (*x as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(TABLE[(group >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
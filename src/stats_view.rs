@@ -0,0 +1,299 @@
+use std::collections::HashMap;
+use std::time::Duration;
+use crossterm::event::KeyCode;
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::Span,
+    widgets::{BarChart, Block, Borders, List, ListItem, Row, Table},
+    Frame,
+};
+
+use crate::{get_word_state, hint, App};
+use crate::player::Game;
+use crate::timer::{format_duration, Speedrun};
+
+fn is_win(game: &Game) -> bool {
+    game.guesses.last() == Some(&game.answer)
+}
+
+/// (answer, played, won, average guess times) sorted from hardest to easiest
+pub fn word_difficulty(games: &[Game]) -> Vec<(String, i32, i32, f64)> {
+    let mut word_map: HashMap<String, (i32, i32, usize)> = HashMap::new();
+    for game in games {
+        let entry = word_map.entry(game.answer.clone()).or_insert((0, 0, 0));
+        entry.0 += 1;
+        if is_win(game) {
+            entry.1 += 1;
+        }
+        entry.2 += game.guesses.len();
+    }
+
+    let mut difficulty: Vec<(String, i32, i32, f64)> = word_map
+        .into_iter()
+        .map(|(answer, (played, won, times))| (answer, played, won, times as f64 / played as f64))
+        .collect();
+    difficulty.sort_by(|a, b| b.3.total_cmp(&a.3).then(a.0.cmp(&b.0)));
+    difficulty
+}
+
+/// (first guess, count) sorted by count
+pub fn first_guess_frequency(games: &[Game]) -> Vec<(String, i32)> {
+    let mut first_map: HashMap<String, i32> = HashMap::new();
+    for game in games {
+        if let Some(first) = game.guesses.first() {
+            *first_map.entry(first.clone()).or_insert(0) += 1;
+        }
+    }
+
+    let mut frequency: Vec<(String, i32)> = first_map.into_iter().collect();
+    frequency.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    frequency
+}
+
+/// for each letter, (times guessed, times it is G or Y)
+pub fn letter_hit_rates(games: &[Game]) -> Vec<(i32, i32)> {
+    let mut hit_rates = vec![(0, 0); 26];
+    for game in games {
+        for guess in &game.guesses {
+            if guess.len() != 5 || game.answer.len() != 5 || !guess.bytes().all(|x| x.is_ascii_uppercase()) {
+                continue;
+            }
+            let word_state = get_word_state(&game.answer, guess);
+            for (letter, state) in guess.bytes().zip(word_state) {
+                let rate = &mut hit_rates[letter as usize - 65];
+                rate.0 += 1;
+                if state == b'G' || state == b'Y' {
+                    rate.1 += 1;
+                }
+            }
+        }
+    }
+    hit_rates
+}
+
+/// (played, won, average guess times of won games) for hard mode or normal mode
+pub fn mode_split(games: &[Game], difficult: bool) -> (i32, i32, f64) {
+    let mut played = 0;
+    let mut won = 0;
+    let mut win_times = 0;
+    for game in games.iter().filter(|game| game.difficult == difficult) {
+        played += 1;
+        if is_win(game) {
+            won += 1;
+            win_times += game.guesses.len();
+        }
+    }
+    let average_times = if won == 0 { 0.0 } else { win_times as f64 / won as f64 };
+    (played, won, (average_times * 100.0).round() / 100.0)
+}
+
+/// (answer, guess times, milliseconds) of won games with recorded times, fastest first
+pub fn fastest_wins(games: &[Game]) -> Vec<(String, usize, u64)> {
+    let mut wins: Vec<(String, usize, u64)> = games
+        .iter()
+        .filter(|game| is_win(game))
+        .filter_map(|game| game.times.last().map(|millis| (game.answer.clone(), game.guesses.len(), *millis)))
+        .collect();
+    wins.sort_by(|a, b| a.2.cmp(&b.2).then(a.1.cmp(&b.1)));
+    wins
+}
+
+/// speedruns with more puzzles first, then fastest first
+pub fn best_speedruns(speedruns: &[Speedrun]) -> Vec<Speedrun> {
+    let mut best = speedruns.to_vec();
+    best.sort_by(|a, b| b.puzzles.cmp(&a.puzzles).then(a.millis.cmp(&b.millis)));
+    best
+}
+
+/// keys on statistics screen
+pub fn handle_key(app: &mut App, code: KeyCode) {
+    let len = app.games.len();
+    let selected = app.history_state.selected().unwrap_or(0);
+    let selected = match code {
+        KeyCode::Tab | KeyCode::Esc => {
+            app.screen = app.home;
+            return;
+        }
+        KeyCode::Up => selected.saturating_sub(1),
+        KeyCode::Down => selected + 1,
+        KeyCode::PageUp => selected.saturating_sub(10),
+        KeyCode::PageDown => selected + 10,
+        KeyCode::Home => 0,
+        KeyCode::End => len,
+        _ => selected,
+    };
+    if len == 0 {
+        app.history_state.select(None);
+    } else {
+        app.history_state.select(Some(selected.min(len - 1)));
+    }
+}
+
+/// statistics screen
+pub fn draw_stats<B: Backend>(f: &mut Frame<B>, app: &mut App) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Statistics (Tab/Esc: back, Up/Down: scroll history)");
+    let area = block.inner(f.size());
+    f.render_widget(block, f.size());
+
+    let chunks = Layout::default()
+        .constraints([Constraint::Length(10), Constraint::Length(8), Constraint::Min(5)].as_ref())
+        .direction(Direction::Vertical)
+        .split(area);
+
+    let bottom_chunks = Layout::default()
+        .constraints([Constraint::Min(30), Constraint::Length(36)].as_ref())
+        .direction(Direction::Horizontal)
+        .split(chunks[2]);
+
+    draw_tables(f, app, chunks[0]);
+    draw_letter_hit_rates(f, app, chunks[1]);
+    draw_history(f, app, bottom_chunks[0]);
+    draw_leaderboards(f, app, bottom_chunks[1]);
+}
+
+fn draw_leaderboards<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+    let chunks = Layout::default()
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
+        .direction(Direction::Vertical)
+        .split(area);
+    let header_style = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+
+    let rows: Vec<Row> = fastest_wins(&app.games)
+        .into_iter()
+        .map(|(answer, times, millis)| {
+            Row::new(vec![answer, format!("{}/6", times), format_duration(Duration::from_millis(millis))])
+        })
+        .collect();
+    let widths = [Constraint::Length(7), Constraint::Length(6), Constraint::Length(10)];
+    let table = Table::new(rows)
+        .header(Row::new(vec!["Answer", "Times", "Time"]).style(header_style))
+        .block(Block::default().borders(Borders::ALL).title("Fastest Wins"))
+        .widths(&widths);
+    f.render_widget(table, chunks[0]);
+
+    let rows: Vec<Row> = best_speedruns(&app.speedruns)
+        .into_iter()
+        .map(|run| {
+            Row::new(vec![run.puzzles.to_string(), format_duration(Duration::from_millis(run.millis)), run.day.to_string()])
+        })
+        .collect();
+    let widths = [Constraint::Length(8), Constraint::Length(10), Constraint::Length(6)];
+    let table = Table::new(rows)
+        .header(Row::new(vec!["Puzzles", "Time", "Day"]).style(header_style))
+        .block(Block::default().borders(Borders::ALL).title("Best Speedruns"))
+        .widths(&widths);
+    f.render_widget(table, chunks[1]);
+}
+
+fn draw_tables<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+    let chunks = Layout::default()
+        .constraints([Constraint::Percentage(32), Constraint::Percentage(20), Constraint::Percentage(26), Constraint::Percentage(22)].as_ref())
+        .direction(Direction::Horizontal)
+        .split(area);
+    let header_style = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+
+    // per-word difficulty
+    let rows: Vec<Row> = word_difficulty(&app.games)
+        .into_iter()
+        .map(|(answer, played, won, average)| {
+            Row::new(vec![answer, played.to_string(), won.to_string(), format!("{:.2}", average)])
+        })
+        .collect();
+    let widths = [Constraint::Length(7), Constraint::Length(7), Constraint::Length(5), Constraint::Length(9)];
+    let table = Table::new(rows)
+        .header(Row::new(vec!["Answer", "Played", "Won", "Avg"]).style(header_style))
+        .block(Block::default().borders(Borders::ALL).title("Word Difficulty"))
+        .widths(&widths);
+    f.render_widget(table, chunks[0]);
+
+    // first guess frequency
+    let rows: Vec<Row> = first_guess_frequency(&app.games)
+        .into_iter()
+        .map(|(word, count)| Row::new(vec![word, count.to_string()]))
+        .collect();
+    let widths = [Constraint::Length(7), Constraint::Length(6)];
+    let table = Table::new(rows)
+        .header(Row::new(vec!["Word", "Times"]).style(header_style))
+        .block(Block::default().borders(Borders::ALL).title("First Guess"))
+        .widths(&widths);
+    f.render_widget(table, chunks[1]);
+
+    // hard mode vs normal mode
+    let rows: Vec<Row> = [("Normal", false), ("Hard", true)]
+        .iter()
+        .map(|(name, difficult)| {
+            let (played, won, average) = mode_split(&app.games, *difficult);
+            Row::new(vec![name.to_string(), played.to_string(), won.to_string(), average.to_string()])
+        })
+        .collect();
+    let widths = [Constraint::Length(7), Constraint::Length(7), Constraint::Length(5), Constraint::Length(6)];
+    let table = Table::new(rows)
+        .header(Row::new(vec!["Mode", "Played", "Won", "Avg"]).style(header_style))
+        .block(Block::default().borders(Borders::ALL).title("Hard vs Normal"))
+        .widths(&widths);
+    f.render_widget(table, chunks[2]);
+
+    // games with each tier of hints
+    let rows: Vec<Row> = hint::TIERS
+        .iter()
+        .zip(hint::tier_counts(&app.games))
+        .map(|(tier, count)| Row::new(vec![tier.to_string(), count.to_string()]))
+        .collect();
+    let widths = [Constraint::Length(11), Constraint::Length(6)];
+    let table = Table::new(rows)
+        .header(Row::new(vec!["Hint", "Games"]).style(header_style))
+        .block(Block::default().borders(Borders::ALL).title("Hints"))
+        .widths(&widths);
+    f.render_widget(table, chunks[3]);
+}
+
+fn draw_letter_hit_rates<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+    let labels: Vec<String> = (b'A'..=b'Z').map(|x| (x as char).to_string()).collect();
+    let data: Vec<(&str, u64)> = labels
+        .iter()
+        .zip(letter_hit_rates(&app.games))
+        .map(|(label, (used, hit))| {
+            let rate = if used == 0 { 0 } else { hit * 100 / used };
+            (label.as_str(), rate as u64)
+        })
+        .collect();
+    let barchart = BarChart::default()
+        .block(Block::default().borders(Borders::ALL).title("Letter Hit Rates (%)"))
+        .data(&data)
+        .max(100)
+        .bar_width(3)
+        .bar_gap(1)
+        .bar_style(Style::default().fg(app.theme.present))
+        .value_style(Style::default().fg(app.theme.foreground).bg(app.theme.present));
+    f.render_widget(barchart, area);
+}
+
+fn draw_history<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
+    let items: Vec<ListItem> = app.games
+        .iter()
+        .enumerate()
+        .map(|(index, game)| {
+            let times = if is_win(game) { game.guesses.len().to_string() } else { "X".to_string() };
+            let hard = if game.difficult { "*" } else { " " };
+            let hints = if game.hints > 0 { format!("h{}", game.hints) } else { "  ".to_string() };
+            ListItem::new(Span::raw(format!(
+                "{:>4}  {}  {}/6{} {}  {}",
+                index + 1,
+                game.answer,
+                times,
+                hard,
+                hints,
+                game.guesses.join(" ")
+            )))
+        })
+        .collect();
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title("History"))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("> ");
+    f.render_stateful_widget(list, area, &mut app.history_state);
+}
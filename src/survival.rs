@@ -0,0 +1,65 @@
+use crate::App;
+use crate::player::Player;
+
+/// progress of an endless run, --survival
+#[derive(Default)]
+pub struct Survival {
+    /// puzzles solved in this run
+    pub solved: usize,
+    /// answer of the last solved puzzle
+    pub previous: Option<String>,
+}
+
+/// green letters of the winning row of the previous puzzle, pre-placed on the first row of the next puzzle,
+/// so the first guess of every puzzle after the first one is the previous answer
+pub fn carried_greens(app: &App) -> Vec<Option<u8>> {
+    match &app.survival {
+        Some(Survival { previous: Some(previous), .. }) => previous.to_uppercase().bytes().map(Some).collect(),
+        _ => vec![None; 5],
+    }
+}
+
+/// number of letters to type besides the carried ones
+pub fn free_slots(carried: &[Option<u8>]) -> usize {
+    carried.iter().filter(|x| x.is_none()).count()
+}
+
+/// the guess with `typed` filled into the positions not carried, and its word state to draw
+pub fn fill(typed: &str, carried: &[Option<u8>]) -> (String, Vec<u8>) {
+    if free_slots(carried) == carried.len() {
+        return (typed.to_string(), vec![b'X'; typed.len()]);
+    }
+    let mut typed = typed.chars();
+    let mut word = String::new();
+    let mut word_state = Vec::new();
+    for letter in carried {
+        match letter {
+            Some(letter) => {
+                word.push(*letter as char);
+                word_state.push(b'G');
+            }
+            None => {
+                word.push(typed.next().unwrap_or(' '));
+                word_state.push(b'X');
+            }
+        }
+    }
+    (word, word_state)
+}
+
+/// count the last game in the run, the run ends on the first failure
+pub fn count_game(player: &mut Player, app: &mut App) {
+    let survival = match &mut app.survival {
+        Some(survival) => survival,
+        None => return,
+    };
+    let game = &player.games[player.games.len() - 1];
+    if game.guesses.last() != Some(&game.answer) {
+        app.message += &format!("\nSurvival over: {} puzzles solved, best run {}", survival.solved, player.best_survival);
+        app.survival = None;
+        return;
+    }
+    survival.solved += 1;
+    survival.previous = Some(game.answer.clone());
+    player.best_survival = player.best_survival.max(survival.solved);
+}
@@ -0,0 +1,206 @@
+use std::fs::File;
+use std::io::Read;
+use serde::Deserialize;
+use tui::style::{Color, Modifier, Style};
+
+/// colors of the terminal ui
+#[derive(Clone)]
+pub struct Theme {
+    /// background of Input and Keyboard panel
+    pub background: Color,
+    /// text of Input and Keyboard panel
+    pub foreground: Color,
+    /// text of message panel
+    pub message: Color,
+    /// G
+    pub correct: Color,
+    /// Y
+    pub present: Color,
+    /// R
+    pub absent: Color,
+    /// X
+    pub unknown: Color,
+    /// mark letter state with symbols, for colour-blind or monochrome terminals
+    pub symbols: bool,
+}
+
+/// theme json, missing colors are taken from the default theme
+#[derive(Deserialize)]
+struct ThemeFile {
+    background: Option<String>,
+    foreground: Option<String>,
+    message: Option<String>,
+    correct: Option<String>,
+    present: Option<String>,
+    absent: Option<String>,
+    unknown: Option<String>,
+    symbols: Option<bool>,
+}
+
+impl Default for Theme {
+    fn default() -> Theme {
+        Theme {
+            background: Color::White,
+            foreground: Color::Black,
+            message: Color::Reset,
+            correct: Color::Green,
+            present: Color::LightYellow,
+            absent: Color::Red,
+            unknown: Color::Gray,
+            symbols: false,
+        }
+    }
+}
+
+impl Theme {
+    pub fn high_contrast() -> Theme {
+        Theme {
+            background: Color::Black,
+            foreground: Color::White,
+            message: Color::White,
+            correct: Color::Rgb(245, 121, 58),
+            present: Color::Rgb(133, 192, 249),
+            absent: Color::DarkGray,
+            unknown: Color::Gray,
+            symbols: false,
+        }
+    }
+
+    pub fn monochrome() -> Theme {
+        Theme {
+            background: Color::Reset,
+            foreground: Color::Reset,
+            message: Color::Reset,
+            correct: Color::Reset,
+            present: Color::Reset,
+            absent: Color::Reset,
+            unknown: Color::Reset,
+            symbols: true,
+        }
+    }
+
+    /// built-in theme by name, otherwise read the theme json
+    pub fn load(theme: &Option<String>) -> Result<Theme, Box<dyn std::error::Error>> {
+        match theme.as_deref() {
+            None | Some("default") => Ok(Theme::default()),
+            Some("high-contrast") => Ok(Theme::high_contrast()),
+            Some("monochrome") => Ok(Theme::monochrome()),
+            Some(file_path) => {
+                let mut file = File::open(file_path)?;
+                let mut contents = String::new();
+                file.read_to_string(&mut contents)?;
+                let theme_file: ThemeFile = serde_json::from_str(&contents)?;
+
+                let mut theme = Theme::default();
+                let colors = [
+                    (&theme_file.background, &mut theme.background),
+                    (&theme_file.foreground, &mut theme.foreground),
+                    (&theme_file.message, &mut theme.message),
+                    (&theme_file.correct, &mut theme.correct),
+                    (&theme_file.present, &mut theme.present),
+                    (&theme_file.absent, &mut theme.absent),
+                    (&theme_file.unknown, &mut theme.unknown),
+                ];
+                for (name, color) in colors {
+                    if let Some(name) = name {
+                        *color = parse_color(name)?;
+                    }
+                }
+                if let Some(symbols) = theme_file.symbols {
+                    theme.symbols = symbols;
+                }
+                Ok(theme)
+            }
+        }
+    }
+
+    /// color of G/Y/R/X
+    pub fn state_color(&self, state: u8) -> Color {
+        match state {
+            71 => self.correct,
+            89 => self.present,
+            82 => self.absent,
+            _ => self.unknown,
+        }
+    }
+
+    /// style of a letter with state G/Y/R/X
+    pub fn state_style(&self, state: u8) -> Style {
+        let style = Style::default().fg(self.state_color(state)).add_modifier(Modifier::BOLD);
+        if !self.symbols {
+            return style;
+        }
+        match state {
+            71 => style.add_modifier(Modifier::REVERSED),
+            89 => style.add_modifier(Modifier::UNDERLINED),
+            82 => style.add_modifier(Modifier::DIM).remove_modifier(Modifier::BOLD),
+            _ => style,
+        }
+    }
+
+    /// style of a tile with state G/Y/R/X
+    pub fn tile_style(&self, state: u8) -> Style {
+        if self.symbols {
+            return self.state_style(state);
+        }
+        match state {
+            71 | 89 | 82 => Style::default().bg(self.state_color(state)).fg(self.foreground).add_modifier(Modifier::BOLD),
+            _ => Style::default().bg(self.background).fg(self.foreground).add_modifier(Modifier::BOLD),
+        }
+    }
+
+    /// symbol shown after a letter with state G/Y/R/X
+    pub fn state_symbol(&self, state: u8) -> &'static str {
+        if !self.symbols {
+            return "";
+        }
+        match state {
+            71 => "=",
+            89 => "?",
+            82 => "x",
+            _ => " ",
+        }
+    }
+
+    /// style of Input and Keyboard panel
+    pub fn panel_style(&self) -> Style {
+        Style::default().bg(self.background).fg(self.foreground)
+    }
+
+    /// style of message panel
+    pub fn message_style(&self) -> Style {
+        Style::default().fg(self.message).add_modifier(Modifier::BOLD)
+    }
+}
+
+/// color names like "green", "#f5793a" or 256-color index like "208"
+fn parse_color(name: &str) -> Result<Color, Box<dyn std::error::Error>> {
+    let color = match name.to_lowercase().replace(['-', '_', ' '], "").as_str() {
+        "reset" => Color::Reset,
+        "black" => Color::Black,
+        "red" => Color::Red,
+        "green" => Color::Green,
+        "yellow" => Color::Yellow,
+        "blue" => Color::Blue,
+        "magenta" => Color::Magenta,
+        "cyan" => Color::Cyan,
+        "gray" => Color::Gray,
+        "darkgray" => Color::DarkGray,
+        "lightred" => Color::LightRed,
+        "lightgreen" => Color::LightGreen,
+        "lightyellow" => Color::LightYellow,
+        "lightblue" => Color::LightBlue,
+        "lightmagenta" => Color::LightMagenta,
+        "lightcyan" => Color::LightCyan,
+        "white" => Color::White,
+        hex if hex.starts_with('#') && hex.len() == 7 => {
+            let rgb = u32::from_str_radix(&hex[1..], 16)?;
+            Color::Rgb((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8)
+        }
+        index => match index.parse::<u8>() {
+            Ok(index) => Color::Indexed(index),
+            Err(_) => return Err(format!("unknown color {}!", name).into()),
+        },
+    };
+    Ok(color)
+}
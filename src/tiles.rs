@@ -0,0 +1,132 @@
+use std::{thread, time::Duration};
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    style::Style,
+    text::{Span, Spans},
+    widgets::{Block, Borders, Paragraph},
+    Frame, Terminal,
+};
+
+use crate::{ui, App};
+
+/// max guess times, rows of the tile grid
+const ROWS: usize = 6;
+/// height of the input panel to draw boxed tiles
+pub const BOXED_HEIGHT: u16 = ROWS as u16 * 3 + 2;
+/// time of one animation frame
+const TICK: Duration = Duration::from_millis(40);
+/// frames to flip one tile
+const FLIP_FRAMES: u16 = 3;
+/// x offset of each frame when shaking a row
+const SHAKE_OFFSETS: [i16; 8] = [-2, 2, -2, 2, -1, 1, -1, 0];
+
+/// animation of a row in the tile grid
+#[derive(Clone, Copy)]
+pub enum Animation {
+    /// flip tiles one by one to reveal the word state
+    Reveal { row: usize, frame: u16 },
+    /// shake the row of an invalid word
+    Shake { row: usize, frame: u16 },
+}
+
+/// play an animation frame by frame
+pub fn animate<B: Backend>(terminal: &mut Terminal<B>, app: &mut App, animation: Animation) -> Result<(), Box<dyn std::error::Error>> {
+    let frames = match animation {
+        Animation::Reveal { .. } => FLIP_FRAMES * 5,
+        Animation::Shake { .. } => SHAKE_OFFSETS.len() as u16,
+    };
+    for frame in 0..frames {
+        app.animation = Some(match animation {
+            Animation::Reveal { row, .. } => Animation::Reveal { row, frame },
+            Animation::Shake { row, .. } => Animation::Shake { row, frame },
+        });
+        terminal.draw(|f| ui(f, app))?;
+        thread::sleep(TICK);
+    }
+    app.animation = None;
+    Ok(())
+}
+
+/// how a tile looks in the current animation frame
+enum TileFace {
+    /// typed but not submitted, or not revealed yet
+    Hidden,
+    /// in the middle of flipping, the letter can't be seen
+    Flipping,
+    Revealed,
+}
+
+fn tile_face(app: &App, row: usize, column: usize) -> TileFace {
+    match app.animation {
+        Some(Animation::Reveal { row: reveal_row, frame }) if reveal_row == row => {
+            let column = column as u16;
+            if frame < column * FLIP_FRAMES {
+                TileFace::Hidden
+            } else if frame < (column + 1) * FLIP_FRAMES {
+                TileFace::Flipping
+            } else {
+                TileFace::Revealed
+            }
+        }
+        _ => TileFace::Revealed,
+    }
+}
+
+fn shake_offset(app: &App, row: usize) -> i16 {
+    match app.animation {
+        Some(Animation::Shake { row: shake_row, frame }) if shake_row == row => SHAKE_OFFSETS[frame as usize],
+        _ => 0,
+    }
+}
+
+/// input panel with one row of tiles for every guess, boxed tiles if there is enough height
+pub fn draw_tiles<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+    let block = Block::default().borders(Borders::ALL).title("Input").style(app.theme.panel_style());
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let boxed = area.height >= BOXED_HEIGHT;
+    let symbol_width = app.theme.state_symbol(b'X').len() as u16;
+    let (tile_width, tile_height, gap) = if boxed { (5, 3, 1) } else { (3 + symbol_width, 1, 1) };
+    let row_width = tile_width * 5 + gap * 4;
+    if row_width + 4 > inner.width {
+        return;
+    }
+
+    for row in 0..ROWS {
+        let y = inner.y + row as u16 * tile_height;
+        if y + tile_height > inner.y + inner.height {
+            break;
+        }
+        let word: Vec<char> = app.guess_words.get(row).map(|x| x.to_uppercase().chars().collect()).unwrap_or_default();
+        let word_state: &[u8] = app.word_states.get(row).map(|x| x.as_slice()).unwrap_or(&[]);
+        let x = (inner.x + (inner.width - row_width) / 2) as i16 + shake_offset(app, row);
+
+        for column in 0..5 {
+            let letter = word.get(column).copied().unwrap_or(' ');
+            let mut state = word_state.get(column).copied().unwrap_or(b'X');
+            let face = tile_face(app, row, column);
+            if let TileFace::Hidden = face {
+                state = b'X';
+            }
+            let label = match face {
+                TileFace::Flipping => Spans::from(""),
+                _ => Spans::from(vec![
+                    Span::raw(letter.to_string()),
+                    Span::raw(if letter == ' ' { "" } else { app.theme.state_symbol(state) }),
+                ]),
+            };
+
+            let tile_area = Rect::new((x + ((tile_width + gap) * column as u16) as i16) as u16, y, tile_width, tile_height);
+            let style = app.theme.tile_style(state);
+            let paragraph = Paragraph::new(label).style(style).alignment(Alignment::Center);
+            if boxed {
+                let border_style = if letter == ' ' { Style::default().fg(app.theme.unknown) } else { style };
+                f.render_widget(paragraph.block(Block::default().borders(Borders::ALL).border_style(border_style)), tile_area);
+            } else {
+                f.render_widget(paragraph, tile_area);
+            }
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use std::time::{Duration, Instant};
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    style::Modifier,
+    text::Span,
+    widgets::Paragraph,
+    Frame,
+};
+
+use crate::App;
+use crate::player::Player;
+
+/// time between redraws of a running clock
+pub const CLOCK_TICK: Duration = Duration::from_millis(200);
+
+/// clock of one game, time paused is not counted
+pub struct Timer {
+    start: Instant,
+    paused: Duration,
+    paused_at: Option<Instant>,
+    /// time limit of the game, --timed
+    pub limit: Option<Duration>,
+}
+
+impl Timer {
+    pub fn new(limit: Option<Duration>) -> Timer {
+        Timer {
+            start: Instant::now(),
+            paused: Duration::ZERO,
+            paused_at: None,
+            limit,
+        }
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        let paused = match self.paused_at {
+            Some(paused_at) => self.paused + paused_at.elapsed(),
+            None => self.paused,
+        };
+        self.start.elapsed().saturating_sub(paused)
+    }
+
+    pub fn pause(&mut self) {
+        if self.paused_at.is_none() {
+            self.paused_at = Some(Instant::now());
+        }
+    }
+
+    pub fn resume(&mut self) {
+        if let Some(paused_at) = self.paused_at.take() {
+            self.paused += paused_at.elapsed();
+        }
+    }
+
+    pub fn time_up(&self) -> bool {
+        match self.limit {
+            Some(limit) => self.elapsed() >= limit,
+            None => false,
+        }
+    }
+}
+
+/// progress of a speedrun, --speedrun
+pub struct Run {
+    pub total: usize,
+    pub solved: usize,
+    /// time of the solved puzzles
+    pub time: Duration,
+    /// day of the first puzzle
+    pub day: i32,
+}
+
+impl Run {
+    pub fn new(total: usize, day: i32) -> Run {
+        Run { total, solved: 0, time: Duration::ZERO, day }
+    }
+}
+
+/// a finished speedrun, recorded in the state json
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Speedrun {
+    pub day: i32,
+    pub puzzles: usize,
+    pub millis: u64,
+}
+
+/// "m:ss.t"
+pub fn format_duration(duration: Duration) -> String {
+    let tenths = duration.as_millis() / 100;
+    format!("{}:{:02}.{}", tenths / 600, tenths / 10 % 60, tenths % 10)
+}
+
+/// the clock is shown in timed games, speedruns and the count down to the next daily puzzle
+pub fn clock_shown(app: &App) -> bool {
+    app.run.is_some() || app.next_daily.is_some() || matches!(&app.timer, Some(timer) if timer.limit.is_some())
+}
+
+/// clock on the top border of `area`, the time left in timed games, the total time of a speedrun
+/// and the count down to the next daily puzzle
+pub fn draw_clock<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+    let mut clock = String::new();
+    if let Some(timer) = &app.timer {
+        if let Some(limit) = timer.limit {
+            clock += &format!(" Left {} ", format_duration(limit.saturating_sub(timer.elapsed())));
+        } else if app.run.is_some() {
+            clock += &format!(" Time {} ", format_duration(timer.elapsed()));
+        }
+        if let Some(run) = &app.run {
+            clock += &format!(" Run {}/{} {} ", run.solved + 1, run.total, format_duration(run.time + timer.elapsed()));
+        }
+    }
+    if let Some(next_daily) = app.next_daily {
+        let seconds = (next_daily - Local::now()).num_seconds().max(0);
+        clock += &format!(" Next puzzle in {}:{:02}:{:02} ", seconds / 3600, seconds / 60 % 60, seconds % 60);
+    }
+    if clock.is_empty() {
+        return;
+    }
+
+    let width = (clock.chars().count() as u16).min(area.width.saturating_sub(2));
+    let clock_area = Rect::new(area.x + area.width - width - 1, area.y, width, 1);
+    let paragraph = Paragraph::new(Span::styled(clock, app.theme.message_style().add_modifier(Modifier::REVERSED)));
+    f.render_widget(paragraph, clock_area);
+}
+
+/// count the last game in the speedrun, the run ends on the first failure or after all puzzles are solved
+pub fn count_run_game(player: &mut Player, app: &mut App) {
+    let run = match &mut app.run {
+        Some(run) => run,
+        None => return,
+    };
+    let game = &player.games[player.games.len() - 1];
+    if game.guesses.last() != Some(&game.answer) {
+        app.message += &format!("\nSpeedrun over: {} of {} puzzles solved", run.solved, run.total);
+        app.run = None;
+        return;
+    }
+    run.solved += 1;
+    run.time += Duration::from_millis(game.times.last().copied().unwrap_or(0));
+    if run.solved < run.total {
+        return;
+    }
+
+    app.message += &format!("\nSpeedrun finished: {} puzzles in {}", run.total, format_duration(run.time));
+    player.speedruns.push(Speedrun { day: run.day, puzzles: run.total, millis: run.time.as_millis() as u64 });
+    app.speedruns = player.speedruns.clone();
+    app.run = None;
+}